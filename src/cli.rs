@@ -1,10 +1,14 @@
-use clap::{Parser, Subcommand, ValueEnum};
+use crate::csv_modifier::{CsvDialect, OutputFormat};
+use crate::item_csv_generator::{DateAverageRounding, ItemAggregate, ItemErrorPolicy, SeasonConvention};
+use crate::modifiers::{parse_exec_modifier, parse_model_rename};
+use clap::{ArgGroup, Parser, Subcommand, ValueEnum};
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 #[command(name = "organise")]
 #[command(about = "A tool for processing and organizing CSV files")]
 #[command(subcommand_negates_reqs = true)]
+#[command(group(ArgGroup::new("batch_fetch").args(["all_tabs", "url_file"]).multiple(true)))]
 pub struct Cli {
     #[command(subcommand)]
     pub command: Option<Commands>,
@@ -13,7 +17,7 @@ pub struct Cli {
     #[arg(
         value_name = "INPUT",
         conflicts_with = "url",
-        required_unless_present_any = ["url"]
+        required_unless_present_any = ["url", "url_file"]
     )]
     pub input: Option<String>,
 
@@ -21,6 +25,30 @@ pub struct Cli {
     #[arg(long, value_name = "URL", conflicts_with = "input")]
     pub url: Option<String>,
 
+    /// Fetch every worksheet tab of the --url workbook and concatenate them (headers must match)
+    #[arg(long, requires = "url")]
+    pub all_tabs: bool,
+
+    /// Fetch only the worksheet tab with this gid (the number after `gid=` in the sheet's URL)
+    #[arg(long, value_name = "GID", requires = "url", conflicts_with = "all_tabs")]
+    pub gid: Option<String>,
+
+    /// Print the CSV export URL for --url (and --gid) and exit without fetching
+    #[arg(long, requires = "url", conflicts_with = "all_tabs")]
+    pub print_url_only: bool,
+
+    /// Process every Google Sheets URL listed in PATH (one per line; blank lines and # comments skipped), each to <spreadsheet-id>-modified.csv
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = ["input", "url", "output", "items_output", "crosswalk", "dedup_kept_output", "items_ledger"]
+    )]
+    pub url_file: Option<String>,
+
+    /// With --url-file or --all-tabs, fetch up to N sheets or tabs concurrently before processing them in order
+    #[arg(long, value_name = "N", default_value_t = 1, requires = "batch_fetch")]
+    pub fetch_concurrency: usize,
+
     /// Path to output CSV file (defaults vary based on input type)
     #[arg(short, long)]
     pub output: Option<String>,
@@ -45,6 +73,109 @@ pub struct Cli {
     #[arg(long, value_name = "URL")]
     pub language_url: Option<String>,
 
+    /// Relabel a derived field_model value before writing, as FROM=TO (repeatable)
+    #[arg(long, value_name = "FROM=TO", value_parser = parse_model_rename)]
+    pub rename_model: Vec<(String, String)>,
+
+    /// Replace COLUMN with the output of COMMAND, run per row with the cell on stdin and the row as JSON in ORGANISE_ROW (repeatable; needs --allow-exec)
+    #[arg(long, value_name = "COLUMN=COMMAND", value_parser = parse_exec_modifier)]
+    pub exec_modifier: Vec<(String, String)>,
+
+    /// Allow --exec-modifier to run external commands
+    #[arg(long)]
+    pub allow_exec: bool,
+
+    /// Seconds each --exec-modifier command may run before it is killed and the cell fails validation
+    #[arg(long, value_name = "SECS", default_value_t = 10, requires = "exec_modifier")]
+    pub exec_timeout: u64,
+
+    /// Columns whose non-empty cells must be valid URLs (comma-separated)
+    #[arg(long, value_name = "COLUMNS", value_delimiter = ',')]
+    pub validate_urls: Vec<String>,
+
+    /// Tidy title columns (default title,fileTitle): trim, collapse !!/??, drop a trailing period unless it ends an abbreviation
+    #[arg(
+        long,
+        value_name = "COLUMNS",
+        value_delimiter = ',',
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "title,fileTitle"
+    )]
+    pub tidy_titles: Vec<String>,
+
+    /// Prepend https:// to schemeless values in --validate-urls columns
+    #[arg(long, requires = "validate_urls")]
+    pub fix_missing_scheme: bool,
+
+    /// Write accessIdentifier,kept_row_number for every duplicated accessIdentifier
+    #[arg(long, value_name = "PATH")]
+    pub dedup_kept_output: Option<String>,
+
+    /// Keep at most N accessIdentifiers in memory for duplicate detection; the rest go to a temporary on-disk index
+    #[arg(long, value_name = "N")]
+    pub dedup_memory_cap: Option<usize>,
+
+    /// Write source_row,original_accessIdentifier,parent_id,file for every written row
+    #[arg(long, value_name = "PATH")]
+    pub crosswalk: Option<String>,
+
+    /// Write the processed CSV with a UTF-8 BOM and CRLF line endings for opening in Excel
+    #[arg(long)]
+    pub excel_friendly: bool,
+
+    /// Write a diff-friendly form for Git: rows sorted by accessIdentifier, all fields quoted, LF endings
+    #[arg(long, conflicts_with = "excel_friendly")]
+    pub canonical: bool,
+
+    /// After writing, check that columns no modifier targets match the input (beyond text cleanup)
+    #[arg(long)]
+    pub verify_passthrough: bool,
+
+    /// Format of the processed output; jsonl writes one JSON object per row (not with --full)
+    #[arg(long, value_enum, value_name = "FORMAT", default_value_t = OutputFormat::Csv)]
+    pub output_format: OutputFormat,
+
+    /// Quote character of the input CSV, e.g. ' for single-quoted fields (default ")
+    #[arg(long, value_name = "CHAR", value_parser = CsvDialect::parse_char)]
+    pub quote_char: Option<u8>,
+
+    /// Escape character for quotes inside quoted input fields, e.g. \ (default: doubled quotes)
+    #[arg(long, value_name = "CHAR", value_parser = CsvDialect::parse_char)]
+    pub escape_char: Option<u8>,
+
+    /// Write the processed CSV with the input's --quote-char/--escape-char instead of standard CSV
+    #[arg(long)]
+    pub keep_dialect: bool,
+
+    /// Rejoin a title split by an unquoted comma when a row has exactly one extra field (heuristic; each repair is logged)
+    #[arg(long)]
+    pub repair_unquoted_commas: bool,
+
+    /// Copy each row's input file value into an appended file_original column before it is rewritten
+    #[arg(long)]
+    pub keep_original_file_column: bool,
+
+    /// Columns exempt from all mutation: modifiers, sanitization, ';' replacement (comma-separated; skipped changes are logged)
+    #[arg(long, value_name = "COLUMNS", value_delimiter = ',')]
+    pub protect_columns: Vec<String>,
+
+    /// Keep container rows (accessIdentifier ending in _00 / _000) instead of skipping them
+    #[arg(long)]
+    pub keep_containers: bool,
+
+    /// Fail the run when a file value's suffix disagrees with its file_extension
+    #[arg(long)]
+    pub strict: bool,
+
+    /// Keep running and reprocess the input file whenever it changes (Ctrl-C to exit)
+    #[arg(long, requires = "input")]
+    pub watch: bool,
+
+    /// Log level for modifier activity (which modifiers run, which cells change), independent of RUST_LOG
+    #[arg(long, value_name = "LEVEL")]
+    pub transform_log_level: Option<log::LevelFilter>,
+
     /// Show detailed processing statistics
     #[arg(long)]
     pub stats: bool,
@@ -61,6 +192,38 @@ pub struct Cli {
         requires = "full"
     )]
     pub node: Option<String>,
+
+    /// Column holding per-row item counts to sum for `# of items` when running --full
+    #[arg(long, value_name = "COLUMN", requires = "full")]
+    pub count_column: Option<String>,
+
+    /// Column the parent ID is written to and items are grouped by (defaults to parent_id)
+    #[arg(long, value_name = "COLUMN")]
+    pub parent_id_column: Option<String>,
+
+    /// How season/quarter dates (Spring 2019, Q2 2019) map into field_edtf_date when running --full
+    #[arg(long, value_enum, value_name = "CONVENTION", default_value_t = SeasonConvention::Month, requires = "full")]
+    pub season_dates: SeasonConvention,
+
+    /// How averaged years in field_edtf_date are rounded when running --full
+    #[arg(long, value_enum, value_name = "MODE", default_value_t = DateAverageRounding::Nearest, requires = "full")]
+    pub date_average_rounding: DateAverageRounding,
+
+    /// Add an items column collecting each parent's values of COLUMN, joined by `|` (repeatable)
+    #[arg(long, value_name = "COLUMN[:distinct]", value_parser = ItemAggregate::parse, requires = "full")]
+    pub items_aggregate: Vec<ItemAggregate>,
+
+    /// Use kept container rows as their parent's items row (title source, not counted as items)
+    #[arg(long, requires_all = ["full", "keep_containers"])]
+    pub promote_containers: bool,
+
+    /// Merge into an existing items file when running --full: add to its counts instead of overwriting it
+    #[arg(long, requires = "full")]
+    pub append: bool,
+
+    /// Ledger of rows already counted into the items file, so overlapping --append runs never count a row twice
+    #[arg(long, value_name = "PATH", requires = "append")]
+    pub items_ledger: Option<String>,
 }
 
 #[derive(Clone, Debug, ValueEnum, PartialEq, Eq, Hash)]
@@ -79,13 +242,14 @@ pub enum Modifier {
 pub enum Commands {
     /// Generate items.csv from a modified CSV file
     GenerateItems {
-        /// Path to input CSV file (typically the modified file)
+        /// Path to input CSV file (typically the modified file); several files each get
+        /// `<stem>-items.csv`
         #[arg(
             value_name = "INPUT",
             conflicts_with = "url",
             required_unless_present = "url"
         )]
-        input: Option<String>,
+        input: Vec<String>,
 
         /// Google Sheets URL to source the data from
         #[arg(
@@ -96,12 +260,52 @@ pub enum Commands {
         )]
         url: Option<String>,
 
-        /// Path to output items.csv file (defaults to 'items.csv')
+        /// Path to output items.csv file (defaults to 'items.csv'; single input only)
         #[arg(short, long)]
         output: Option<String>,
 
+        /// Directory for the items files; a relative --output (or the default items.csv) goes under it
+        #[arg(long, value_name = "DIR")]
+        output_dir: Option<String>,
+
+        /// With several inputs, abort on the first failing file or skip it and continue
+        #[arg(long, value_enum, value_name = "POLICY", default_value_t = ItemErrorPolicy::Abort)]
+        on_error: ItemErrorPolicy,
+
         /// Node identifier to populate the field_member_of column
         #[arg(short = 'n', long = "node", value_name = "NODE")]
         node: Option<String>,
+
+        /// Column holding per-row item counts to sum for `# of items` (falls back to row counts)
+        #[arg(long, value_name = "COLUMN")]
+        count_column: Option<String>,
+
+        /// Column to group items by (defaults to parent_id)
+        #[arg(long, value_name = "COLUMN")]
+        parent_id_column: Option<String>,
+
+        /// How season/quarter dates (Spring 2019, Q2 2019) map into field_edtf_date
+        #[arg(long, value_enum, value_name = "CONVENTION", default_value_t = SeasonConvention::Month)]
+        season_dates: SeasonConvention,
+
+        /// How averaged years in field_edtf_date are rounded
+        #[arg(long, value_enum, value_name = "MODE", default_value_t = DateAverageRounding::Nearest)]
+        date_average_rounding: DateAverageRounding,
+
+        /// Add an items column collecting each parent's values of COLUMN, joined by `|` (repeatable)
+        #[arg(long, value_name = "COLUMN[:distinct]", value_parser = ItemAggregate::parse)]
+        items_aggregate: Vec<ItemAggregate>,
+
+        /// Use container rows (accessIdentifier ending in _00 / _000) as their parent's title source
+        #[arg(long)]
+        promote_containers: bool,
+
+        /// Merge into an existing items file: add to its counts instead of overwriting it
+        #[arg(long)]
+        append: bool,
+
+        /// Ledger of rows already counted into the items file, so overlapping --append runs never count a row twice
+        #[arg(long, value_name = "PATH", requires = "append")]
+        items_ledger: Option<String>,
     },
 }
@@ -1,10 +1,133 @@
-use crate::modifiers::{AccessIdentifierValidator, CopyFromColumnModifier};
+use crate::dedup::SeenKeys;
+use crate::modifiers::{
+    AccessIdentifierValidator, CopyFromColumnModifier, DEFAULT_PARENT_ID_COLUMN,
+};
 use anyhow::{Context, Result};
-use csv::{Reader, Writer};
+use clap::ValueEnum;
+use csv::{QuoteStyle, Reader, ReaderBuilder, Terminator, Writer, WriterBuilder};
 use encoding_rs::WINDOWS_1252;
-use log::warn;
-use std::collections::{BTreeMap, HashMap, HashSet};
+use log::{debug, info, warn};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs::File;
+use std::io::{BufWriter, Write};
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Log target for modifier activity (which modifiers run, which cells they change), so it can be
+/// filtered separately from warnings, e.g. `RUST_LOG=warn,organise::transform=debug`.
+pub const TRANSFORM_LOG_TARGET: &str = "organise::transform";
+
+/// Serialization used for the processed rows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// CSV with a header row
+    #[default]
+    Csv,
+    /// One JSON object per row, keyed by header name; values stay strings
+    Jsonl,
+}
+
+/// Quoting conventions of a CSV file. The default is standard CSV: `"` quotes, with embedded
+/// quotes doubled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvDialect {
+    /// Character that encloses quoted fields.
+    pub quote: u8,
+    /// Character escaping a quote inside a quoted field (e.g. `\`). `None` means quotes are
+    /// escaped by doubling them.
+    pub escape: Option<u8>,
+}
+
+impl Default for CsvDialect {
+    fn default() -> Self {
+        Self {
+            quote: b'"',
+            escape: None,
+        }
+    }
+}
+
+impl CsvDialect {
+    /// Parses a `--quote-char` / `--escape-char` value: a single ASCII character other than a
+    /// comma or line break.
+    pub fn parse_char(value: &str) -> Result<u8> {
+        match value.as_bytes() {
+            [c] if c.is_ascii() && !matches!(c, b',' | b'\n' | b'\r') => Ok(*c),
+            _ => anyhow::bail!(
+                "Invalid CSV quote/escape character '{}': expected a single ASCII character other than a comma or line break",
+                value
+            ),
+        }
+    }
+
+    fn reader_builder(&self) -> ReaderBuilder {
+        let mut builder = ReaderBuilder::new();
+        builder.quote(self.quote).escape(self.escape);
+        builder
+    }
+
+    fn writer_builder(&self) -> WriterBuilder {
+        let mut builder = WriterBuilder::new();
+        builder.quote(self.quote);
+        if let Some(escape) = self.escape {
+            builder.double_quote(false).escape(escape);
+        }
+        builder
+    }
+}
+
+/// Destination for processed rows, in the configured [`OutputFormat`].
+enum RowSink {
+    Csv(Box<Writer<File>>),
+    Jsonl {
+        writer: BufWriter<File>,
+        keys: Vec<String>,
+    },
+}
+
+impl RowSink {
+    fn write_row(&mut self, row: &[String]) -> Result<()> {
+        match self {
+            RowSink::Csv(writer) => writer.write_record(row)?,
+            RowSink::Jsonl { writer, keys } => {
+                writer.write_all(json_object_line(keys, row).as_bytes())?;
+            }
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        match self {
+            RowSink::Csv(writer) => writer.flush()?,
+            RowSink::Jsonl { writer, .. } => writer.flush()?,
+        }
+        Ok(())
+    }
+}
+
+/// Renders `row` as a single-line JSON object with keys in header order, followed by `\n`.
+///
+/// Missing trailing cells are written as empty strings, like the CSV writer's padded rows.
+fn json_object_line(keys: &[String], row: &[String]) -> String {
+    format!("{}\n", json_object(keys, row))
+}
+
+/// `{"key":"value",...}` for one row; every value is a JSON string.
+fn json_object(keys: &[String], row: &[String]) -> String {
+    let fields: Vec<String> = keys
+        .iter()
+        .enumerate()
+        .map(|(idx, key)| {
+            let value = row.get(idx).map(String::as_str).unwrap_or("");
+            format!(
+                "{}:{}",
+                serde_json::Value::from(key.as_str()),
+                serde_json::Value::from(value)
+            )
+        })
+        .collect();
+    format!("{{{}}}", fields.join(","))
+}
 
 pub(crate) fn normalize_cell(value: &str) -> &str {
     let trimmed = value.trim();
@@ -61,6 +184,11 @@ fn fix_common_mojibake(value: &str) -> Option<String> {
 }
 
 fn sanitize_text_in_place(value: &mut String) -> bool {
+    // NBSP and every mojibake marker are non-ASCII, so pure ASCII cells never need work.
+    if value.is_ascii() {
+        return false;
+    }
+
     let mut changed = false;
 
     if value.contains('\u{00A0}') {
@@ -78,12 +206,41 @@ fn sanitize_text_in_place(value: &mut String) -> bool {
     changed
 }
 
+/// Returns the suffix of `file_value` when it differs (case-insensitively) from `declared`.
+///
+/// Values without a suffix, or rows without a declared extension, are not compared.
+fn file_suffix_mismatch<'a>(file_value: &'a str, declared: &str) -> Option<&'a str> {
+    let declared = declared.trim().trim_start_matches('.');
+    let file_name = file_value.rsplit('/').next().unwrap_or(file_value);
+    let (stem, suffix) = file_name.rsplit_once('.')?;
+    if declared.is_empty() || stem.is_empty() || suffix.eq_ignore_ascii_case(declared) {
+        return None;
+    }
+    Some(suffix)
+}
+
+/// Description columns keep literal `;`; every other column has `;` replaced with the `|` subdelimiter.
+fn keeps_semicolons(header_name: &str) -> bool {
+    header_name.eq_ignore_ascii_case("field_description")
+        || header_name.eq_ignore_ascii_case("description")
+}
+
 pub trait ColumnModifier {
     fn modify(&self, value: &str, row: &RowContext) -> String;
     fn description(&self) -> &str;
     fn validate(&self, _value: &str, _row: &RowContext) -> bool {
         true
     }
+    /// Explains a failed `validate` call in validation logs; `None` falls back to the generic
+    /// missing-field report.
+    fn validation_reason(&self, _value: &str, _row: &RowContext) -> Option<String> {
+        None
+    }
+    /// Whether to run on a protected column, where only validation has an effect. Return `false`
+    /// when running is costly or has side effects, e.g. an external command.
+    fn runs_on_protected_columns(&self) -> bool {
+        true
+    }
 }
 
 #[derive(Debug)]
@@ -114,6 +271,11 @@ impl<'a> RowContext<'a> {
             .and_then(|i| self.values.get(i).map(|s| s.as_str()))
     }
 
+    /// The row as a JSON object keyed by header, with every value as a string.
+    pub fn to_json(&self) -> String {
+        json_object(self.headers, self.values)
+    }
+
     pub fn get_or_empty(&self, column: &str) -> &str {
         self.get(column).map(normalize_cell).unwrap_or("")
     }
@@ -127,6 +289,25 @@ impl<'a> RowContext<'a> {
     }
 }
 
+/// Column receiving the input `file` value with [`CsvModifier::with_original_file_column`].
+pub const FILE_ORIGINAL_COLUMN: &str = "file_original";
+
+/// Columns checked for a title, in priority order.
+const TITLE_COLUMNS: [&str; 2] = ["title", "fileTitle"];
+
+/// Rejoins a title that an unquoted comma split in two: when `row` has exactly one field more
+/// than `width`, the field after `title_idx` is appended to the title with a comma. Returns
+/// whether the row was repaired.
+fn rejoin_split_title(row: &mut Vec<String>, width: usize, title_idx: usize) -> bool {
+    if row.len() != width + 1 || title_idx + 1 >= row.len() {
+        return false;
+    }
+    let overflow = row.remove(title_idx + 1);
+    row[title_idx].push(',');
+    row[title_idx].push_str(&overflow);
+    true
+}
+
 /// Renames legacy column headers to Drupal-style `field_*` names when the target name is absent.
 fn apply_header_renames(headers: &mut [String], header_map: &mut HashMap<String, usize>) {
     const RENAMES: &[(&str, &str)] = &[
@@ -149,7 +330,22 @@ fn apply_header_renames(headers: &mut [String], header_map: &mut HashMap<String,
 }
 
 pub struct CsvModifier {
-    column_modifiers: BTreeMap<String, Box<dyn ColumnModifier>>,
+    /// Modifier chain per column, applied in insertion order.
+    column_modifiers: BTreeMap<String, Vec<Box<dyn ColumnModifier>>>,
+    parent_id_column: String,
+    dedup_kept_output: Option<String>,
+    crosswalk_output: Option<String>,
+    excel_friendly: bool,
+    canonical: bool,
+    verify_passthrough: bool,
+    output_format: OutputFormat,
+    strict_extensions: bool,
+    input_dialect: CsvDialect,
+    output_dialect: CsvDialect,
+    repair_unquoted_commas: bool,
+    dedup_memory_cap: Option<usize>,
+    keep_original_file: bool,
+    protected_columns: BTreeSet<String>,
 }
 
 impl Default for CsvModifier {
@@ -160,43 +356,343 @@ impl Default for CsvModifier {
 
 impl CsvModifier {
     pub fn new() -> Self {
-        let mut column_modifiers: BTreeMap<String, Box<dyn ColumnModifier>> = BTreeMap::new();
+        let mut column_modifiers: BTreeMap<String, Vec<Box<dyn ColumnModifier>>> =
+            BTreeMap::new();
         column_modifiers.insert(
             "accessIdentifier".to_string(),
-            Box::new(AccessIdentifierValidator),
+            vec![Box::new(AccessIdentifierValidator::default())],
         );
         column_modifiers.insert(
             "field_identifier".to_string(),
-            Box::new(CopyFromColumnModifier::new("accessIdentifier")),
+            vec![Box::new(CopyFromColumnModifier::new("accessIdentifier"))],
         );
         // Intentionally not modifying field_description: no forced quotes or semicolon escaping
 
-        Self { column_modifiers }
+        Self {
+            column_modifiers,
+            parent_id_column: DEFAULT_PARENT_ID_COLUMN.to_string(),
+            dedup_kept_output: None,
+            crosswalk_output: None,
+            excel_friendly: false,
+            canonical: false,
+            verify_passthrough: false,
+            output_format: OutputFormat::Csv,
+            strict_extensions: false,
+            input_dialect: CsvDialect::default(),
+            output_dialect: CsvDialect::default(),
+            repair_unquoted_commas: false,
+            dedup_memory_cap: None,
+            keep_original_file: false,
+            protected_columns: BTreeSet::new(),
+        }
+    }
+
+    /// Keep container rows (`accessIdentifier` ending in `_00` / `_000`) instead of skipping them,
+    /// so item generation can promote them to parent rows. Empty identifiers are still rejected.
+    pub fn with_containers_kept(self) -> Self {
+        self.add_column_modifier("accessIdentifier", AccessIdentifierValidator::new(true))
     }
 
+    /// Set the modifier for a column, replacing any modifiers already registered for it
+    /// (including the built-in defaults). Use [`CsvModifier::chain_column_modifier`] to add to them.
     pub fn add_column_modifier<M>(mut self, column: &str, modifier: M) -> Self
     where
         M: ColumnModifier + 'static,
     {
         self.column_modifiers
-            .insert(column.to_string(), Box::new(modifier));
+            .insert(column.to_string(), vec![Box::new(modifier)]);
+        self
+    }
+
+    /// Append a modifier to a column's chain. Modifiers run in insertion order, each seeing the
+    /// previous one's output.
+    pub fn chain_column_modifier<M>(mut self, column: &str, modifier: M) -> Self
+    where
+        M: ColumnModifier + 'static,
+    {
+        self.column_modifiers
+            .entry(column.to_string())
+            .or_default()
+            .push(Box::new(modifier));
+        self
+    }
+
+    /// Name of the column the parent identifier is written to (default `parent_id`).
+    ///
+    /// The column is added to the output when the input lacks it, like the default `parent_id`.
+    /// Register `ParentIdModifier` on the same column name.
+    pub fn with_parent_id_column(mut self, column: &str) -> Self {
+        self.parent_id_column = column.to_string();
+        self
+    }
+
+    /// Write `accessIdentifier,kept_row_number` for every identifier that appeared more than once.
+    ///
+    /// The kept row is the first occurrence that was written to the output; row numbers are
+    /// 1-based data rows (header excluded), matching the row numbers used in validation logs.
+    pub fn with_dedup_kept_output(mut self, path: &str) -> Self {
+        self.dedup_kept_output = Some(path.to_string());
+        self
+    }
+
+    /// Write `source_row,original_accessIdentifier,parent_id,file` for every written row, pairing
+    /// the derived parent ID and file path with the input row they came from.
+    ///
+    /// `source_row` is the 1-based data row (header excluded) and `original_accessIdentifier` is
+    /// the unmodified input value; rows appear in input order.
+    pub fn with_crosswalk_output(mut self, path: &str) -> Self {
+        self.crosswalk_output = Some(path.to_string());
+        self
+    }
+
+    /// Copy each row's input `file` value into a `file_original` column (appended to the header)
+    /// before modifiers run, so the composed path can be reviewed next to what it came from.
+    pub fn with_original_file_column(mut self) -> Self {
+        self.keep_original_file = true;
+        self
+    }
+
+    /// Exempt columns from every mutation: modifiers, text sanitization and `;` → `|` replacement.
+    ///
+    /// Modifiers registered on a protected column still validate (an invalid `accessIdentifier`
+    /// still skips the row), but changes they would make are logged on [`TRANSFORM_LOG_TARGET`]
+    /// and not written, so the cell reaches the output exactly as read. Modifiers whose
+    /// [`ColumnModifier::runs_on_protected_columns`] is `false`, such as [`ExecModifier`], are not
+    /// run there at all.
+    ///
+    /// [`ExecModifier`]: crate::modifiers::ExecModifier
+    pub fn with_protected_columns<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.protected_columns.extend(columns.into_iter().map(Into::into));
+        self
+    }
+
+    /// Write the processed CSV with a UTF-8 BOM and CRLF line endings so Excel opens it cleanly.
+    /// Cell values are unaffected.
+    pub fn with_excel_friendly_output(mut self) -> Self {
+        self.excel_friendly = true;
+        self
+    }
+
+    /// Write a stable, diff-friendly form for version control: rows sorted by `accessIdentifier`
+    /// (input order is kept when the column is absent), every field quoted, LF line endings, and
+    /// every row padded to the header's field order.
+    ///
+    /// Rows are buffered in memory until the end of the input so they can be sorted. Takes
+    /// precedence over [`CsvModifier::with_excel_friendly_output`].
+    pub fn with_canonical_output(mut self) -> Self {
+        self.canonical = true;
+        self
+    }
+
+    /// After writing, re-read the input and output and fail if any column that no modifier targets
+    /// differs from the input beyond sanitization (NBSP / mojibake) and `;` → `|` replacement.
+    pub fn with_passthrough_verification(mut self) -> Self {
+        self.verify_passthrough = true;
+        self
+    }
+
+    /// Serialize processed rows as `format`. With [`OutputFormat::Jsonl`] each kept row becomes one
+    /// JSON object keyed by header name (values stay strings) and the Excel/quoting options do
+    /// not apply; canonical sorting still does.
+    pub fn with_output_format(mut self, format: OutputFormat) -> Self {
+        self.output_format = format;
+        self
+    }
+
+    /// Read input quoted with `dialect` (e.g. single quotes or backslash escapes). The output stays
+    /// standard CSV unless [`Self::with_output_dialect`] is also set.
+    pub fn with_input_dialect(mut self, dialect: CsvDialect) -> Self {
+        self.input_dialect = dialect;
+        self
+    }
+
+    /// Quote the CSV output with `dialect` instead of standard CSV. Has no effect on JSON lines.
+    pub fn with_output_dialect(mut self, dialect: CsvDialect) -> Self {
+        self.output_dialect = dialect;
+        self
+    }
+
+    /// Recover rows where an unquoted comma in the title split it into two fields: a row with
+    /// exactly one extra field has the field after the `title` (or `fileTitle`) column joined back
+    /// into it with a comma. Each repair is logged and counted in
+    /// [`ProcessingStats::unquoted_comma_repairs`]; rows with any other field count still fail.
+    ///
+    /// This is a heuristic; it assumes the title was the column that split.
+    pub fn with_unquoted_comma_repair(mut self) -> Self {
+        self.repair_unquoted_commas = true;
+        self
+    }
+
+    /// Hold at most `cap` `accessIdentifier` keys in memory for duplicate detection. Beyond that,
+    /// seen keys are moved to an on-disk index in a temporary directory and only an 8-byte hash
+    /// per key stays in memory. Detection remains exact; each duplicate of a spilled key costs a
+    /// read of one index file, so caps far below the number of rows slow down duplicate-heavy input.
+    pub fn with_dedup_memory_cap(mut self, cap: usize) -> Self {
+        self.dedup_memory_cap = Some(cap);
+        self
+    }
+
+    /// Fail processing when any row's `file` suffix disagrees with its declared `file_extension`
+    /// (the mismatches are always logged and counted in [`ProcessingStats::extension_mismatches`]).
+    /// The processed output and crosswalk are removed on failure, and no dedup record is written.
+    pub fn with_strict_extension_check(mut self) -> Self {
+        self.strict_extensions = true;
         self
     }
 
     /// Process CSV from a file path
     pub fn process_file(&self, input_path: &str, output_path: &str) -> Result<ProcessingStats> {
-        let mut reader =
-            Reader::from_reader(File::open(input_path).context("Failed to open input file")?);
-        self.process_csv_reader(&mut reader, output_path)
+        let mut reader = self
+            .input_reader_builder()
+            .from_reader(File::open(input_path).context("Failed to open input file")?);
+        let (stats, written_rows) = self.process_records(&mut reader, output_path)?;
+
+        if self.verify_passthrough {
+            let mut input = self
+                .input_reader_builder()
+                .from_reader(File::open(input_path).context("Failed to open input file")?);
+            self.verify_passthrough_columns(&mut input, output_path, &written_rows)?;
+        }
+
+        Ok(stats)
     }
 
-    /// Internal method to process CSV from any reader
-    pub(crate) fn process_csv_reader<R: std::io::Read>(
+    /// Process CSV held in memory (e.g. a Google Sheets export)
+    pub(crate) fn process_csv_data(
         &self,
-        reader: &mut Reader<R>,
+        csv_data: &str,
         output_path: &str,
     ) -> Result<ProcessingStats> {
+        let mut reader = self.input_reader_builder().from_reader(csv_data.as_bytes());
+        let (stats, written_rows) = self.process_records(&mut reader, output_path)?;
+
+        if self.verify_passthrough {
+            let mut input = self.input_reader_builder().from_reader(csv_data.as_bytes());
+            self.verify_passthrough_columns(&mut input, output_path, &written_rows)?;
+        }
+
+        Ok(stats)
+    }
+
+    /// Reader settings for the input; comma repair needs rows of any length to reach it.
+    fn input_reader_builder(&self) -> ReaderBuilder {
+        let mut builder = self.input_dialect.reader_builder();
+        builder.flexible(self.repair_unquoted_commas);
+        builder
+    }
+
+    /// Compares columns untouched by modifiers between the input and the written output.
+    ///
+    /// `written_rows` lists, in output order, the 0-based input row each output row came from.
+    fn verify_passthrough_columns<R: std::io::Read>(
+        &self,
+        input: &mut Reader<R>,
+        output_path: &str,
+        written_rows: &[usize],
+    ) -> Result<()> {
+        const MAX_REPORTED: usize = 10;
+
+        let input_headers = input.headers()?.clone();
+        let input_width = input_headers.len();
+        let title_idx = TITLE_COLUMNS
+            .iter()
+            .find_map(|name| input_headers.iter().position(|h| h == *name));
+        let mut output = self.output_dialect.reader_builder().from_reader(
+            File::open(output_path).context("Failed to reopen output file for verification")?,
+        );
+        let output_headers = output.headers()?.clone();
+
+        // Leading columns keep their position in the output; derived columns are appended.
+        // Protected columns are checked even when a modifier targets them.
+        let checked_columns: Vec<(usize, String)> = (0..input_width)
+            .filter_map(|idx| output_headers.get(idx).map(|name| (idx, name.to_string())))
+            .filter(|(_, name)| {
+                self.protected_columns.contains(name) || !self.column_modifiers.contains_key(name)
+            })
+            .filter(|(_, name)| !(self.keep_original_file && name == FILE_ORIGINAL_COLUMN))
+            .collect();
+
+        let output_records: Vec<csv::StringRecord> = output
+            .records()
+            .collect::<std::result::Result<_, _>>()
+            .context("Failed to read output file for verification")?;
+        let output_position: HashMap<usize, usize> = written_rows
+            .iter()
+            .enumerate()
+            .map(|(position, &source_row)| (source_row, position))
+            .collect();
+
+        let mut mismatches: Vec<String> = Vec::new();
+        let mut mismatch_count = 0;
+
+        for (row_idx, record) in input.records().enumerate() {
+            let Some(&position) = output_position.get(&row_idx) else {
+                continue;
+            };
+            let mut record: Vec<String> = record?.iter().map(|s| s.to_string()).collect();
+            if self.repair_unquoted_commas {
+                if let Some(title_idx) = title_idx {
+                    rejoin_split_title(&mut record, input_width, title_idx);
+                }
+            }
+            let output_record = output_records.get(position);
+
+            for (col_idx, column_name) in &checked_columns {
+                let mut expected = record.get(*col_idx).cloned().unwrap_or_default();
+                if !self.protected_columns.contains(column_name) {
+                    sanitize_text_in_place(&mut expected);
+                    if !keeps_semicolons(column_name) {
+                        expected = expected.replace(';', "|");
+                    }
+                }
+                let found = output_record.and_then(|r| r.get(*col_idx));
+
+                if found != Some(expected.as_str()) {
+                    mismatch_count += 1;
+                    let message = format!(
+                        "row {}, column '{}': expected '{}', found '{}'",
+                        row_idx + 1,
+                        column_name,
+                        expected,
+                        found.unwrap_or("<missing>")
+                    );
+                    if mismatches.len() < MAX_REPORTED {
+                        warn!("Passthrough verification failed at {}", message);
+                        mismatches.push(message);
+                    }
+                }
+            }
+        }
+
+        if mismatch_count > 0 {
+            anyhow::bail!(
+                "Passthrough verification found {} unexpected change(s) in unmodified or protected columns:\n  - {}{}",
+                mismatch_count,
+                mismatches.join("\n  - "),
+                if mismatch_count > mismatches.len() { "\n  - ..." } else { "" }
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Streams rows from `reader` to `output_path`. Alongside the stats, returns the 0-based input
+    /// row of every written output row (in output order) when passthrough verification is enabled.
+    fn process_records<R: std::io::Read>(
+        &self,
+        reader: &mut Reader<R>,
+        output_path: &str,
+    ) -> Result<(ProcessingStats, Vec<usize>)> {
+        if self.verify_passthrough && self.output_format == OutputFormat::Jsonl {
+            anyhow::bail!("Passthrough verification requires CSV output");
+        }
+
         let headers_snapshot = reader.headers()?.clone();
+        let input_width = headers_snapshot.len();
         let mut headers: Vec<String> = headers_snapshot.iter().map(|h| h.to_string()).collect();
 
         let mut header_map: HashMap<String, usize> = headers
@@ -210,7 +706,7 @@ impl CsvModifier {
         // Ensure columns exist for modifiers that populate derived values when the source CSV
         // (e.g. Google Sheets export) omits them.
         const AUTO_ADD_DERIVED_COLUMNS: &[&str] =
-            &["field_model", "parent_id", "file", "field_language"];
+            &["field_model", DEFAULT_PARENT_ID_COLUMN, "file", "field_language"];
 
         for column_name in self.column_modifiers.keys() {
             if header_map.contains_key(column_name) {
@@ -218,6 +714,8 @@ impl CsvModifier {
             }
             let add = if column_name == "field_identifier" {
                 header_map.contains_key("accessIdentifier")
+            } else if *column_name == self.parent_id_column {
+                true
             } else {
                 AUTO_ADD_DERIVED_COLUMNS.contains(&column_name.as_str())
             };
@@ -227,38 +725,189 @@ impl CsvModifier {
             }
         }
 
-        let title_column = ["title", "fileTitle"]
+        for (column_name, chain) in &self.column_modifiers {
+            for modifier in chain {
+                if header_map.contains_key(column_name) {
+                    info!(
+                        target: TRANSFORM_LOG_TARGET,
+                        "Applying modifier '{}' to column '{}'",
+                        modifier.description(),
+                        column_name
+                    );
+                } else {
+                    debug!(
+                        target: TRANSFORM_LOG_TARGET,
+                        "Column '{}' not in input; modifier '{}' will not run",
+                        column_name,
+                        modifier.description()
+                    );
+                }
+            }
+        }
+
+        for column_name in &self.protected_columns {
+            if header_map.contains_key(column_name) {
+                info!(
+                    target: TRANSFORM_LOG_TARGET,
+                    "Column '{}' is protected; changes to it will be logged but not written",
+                    column_name
+                );
+                for modifier in self.column_modifiers.get(column_name).into_iter().flatten() {
+                    if !modifier.runs_on_protected_columns() {
+                        info!(
+                            target: TRANSFORM_LOG_TARGET,
+                            "Column '{}' is protected; not running '{}'",
+                            column_name,
+                            modifier.description()
+                        );
+                    }
+                }
+            } else {
+                warn!("Protected column '{}' is not in the input.", column_name);
+            }
+        }
+
+        let file_idx = header_map.get("file").copied();
+        let file_original_idx = if self.keep_original_file {
+            Some(match header_map.get(FILE_ORIGINAL_COLUMN) {
+                Some(&idx) => idx,
+                None => {
+                    header_map.insert(FILE_ORIGINAL_COLUMN.to_string(), headers.len());
+                    headers.push(FILE_ORIGINAL_COLUMN.to_string());
+                    headers.len() - 1
+                }
+            })
+        } else {
+            None
+        };
+        let protected: Vec<bool> = headers
             .iter()
-            .find_map(|name| header_map.get(*name).copied().map(|index| (index, *name)));
+            .map(|name| self.protected_columns.contains(name))
+            .collect();
+        let is_protected = |idx: usize| protected.get(idx).copied().unwrap_or(false);
+        let extension_idxs: Vec<usize> = ["file_extension", "file_extention"]
+            .iter()
+            .filter_map(|name| header_map.get(*name).copied())
+            .collect();
 
-        let output_file = File::create(output_path).context("Failed to create output file")?;
-        let mut writer = Writer::from_writer(output_file);
+        let title_column = TITLE_COLUMNS
+            .iter()
+            .find_map(|name| header_map.get(*name).copied().map(|index| (index, *name)));
 
-        // Write headers to output
-        writer.write_record(&headers)?;
+        let mut output_file = File::create(output_path).context("Failed to create output file")?;
+        let mut writer = match self.output_format {
+            OutputFormat::Jsonl => RowSink::Jsonl {
+                writer: BufWriter::new(output_file),
+                keys: headers.clone(),
+            },
+            OutputFormat::Csv => {
+                let terminator = if self.excel_friendly && !self.canonical {
+                    output_file
+                        .write_all(UTF8_BOM)
+                        .context("Failed to write output file")?;
+                    Terminator::CRLF
+                } else {
+                    Terminator::Any(b'\n')
+                };
+                let quote_style = if self.canonical {
+                    QuoteStyle::Always
+                } else {
+                    QuoteStyle::Necessary
+                };
+                let mut csv_writer = self
+                    .output_dialect
+                    .writer_builder()
+                    .terminator(terminator)
+                    .quote_style(quote_style)
+                    .from_writer(output_file);
+
+                // Write headers to output
+                csv_writer.write_record(&headers)?;
+                RowSink::Csv(Box::new(csv_writer))
+            }
+        };
 
         let mut stats = ProcessingStats::new();
 
         // Stream processing for column modifiers
         let mut validation_logging_suppressed = false;
-        let mut seen_access_identifiers: HashSet<String> = HashSet::with_capacity(1024); // Pre-allocate for better performance
+        // accessIdentifier -> row number of the occurrence that was kept
+        let mut seen_access_identifiers = SeenKeys::new(self.dedup_memory_cap);
+        let mut duplicated_access_identifiers: BTreeSet<String> = BTreeSet::new();
+        let mut canonical_rows: Vec<(usize, Vec<String>)> = Vec::new();
+        let mut written_rows: Vec<usize> = Vec::new();
+
+        let mut crosswalk_writer = match &self.crosswalk_output {
+            Some(path) => {
+                let file = File::create(path).context("Failed to create crosswalk output file")?;
+                let mut crosswalk = Writer::from_writer(file);
+                crosswalk.write_record([
+                    "source_row",
+                    "original_accessIdentifier",
+                    DEFAULT_PARENT_ID_COLUMN,
+                    "file",
+                ])?;
+                Some(crosswalk)
+            }
+            None => None,
+        };
+        let access_identifier_idx = header_map.get("accessIdentifier").copied();
+        let crosswalk_parent_idx = header_map.get(&self.parent_id_column).copied();
         for (row_idx, result) in reader.records().enumerate() {
             let record = result?;
             let mut row_values: Vec<String> = record.iter().map(|s| s.to_string()).collect();
+            if self.repair_unquoted_commas && row_values.len() != input_width {
+                let repaired_title = title_column.filter(|&(title_idx, _)| {
+                    rejoin_split_title(&mut row_values, input_width, title_idx)
+                });
+                let Some((title_idx, title_name)) = repaired_title else {
+                    anyhow::bail!(
+                        "Row {} has {} fields but the header has {}; only a single unquoted comma in the title can be repaired",
+                        row_idx + 1,
+                        row_values.len(),
+                        input_width
+                    );
+                };
+                stats.unquoted_comma_repairs += 1;
+                warn!(
+                    "Repaired row {}: rejoined an unquoted comma into {} '{}'.",
+                    row_idx + 1,
+                    title_name,
+                    row_values[title_idx]
+                );
+            }
             if row_values.len() < headers.len() {
                 row_values.resize(headers.len(), String::new());
             }
+            if let (Some(original_idx), Some(file_idx)) = (file_original_idx, file_idx) {
+                row_values[original_idx] = row_values[file_idx].clone();
+            }
             let mut row_valid = true;
             let mut current_access_identifier: Option<String> = None;
             let mut sanitized_cells = 0;
 
-            for cell in row_values.iter_mut() {
+            for (idx, cell) in row_values.iter_mut().enumerate() {
+                if is_protected(idx) {
+                    let mut sanitized = cell.clone();
+                    if sanitize_text_in_place(&mut sanitized) {
+                        info!(
+                            target: TRANSFORM_LOG_TARGET,
+                            "Row {}: protected column '{}' not sanitized ('{}' -> '{}')",
+                            row_idx + 1,
+                            headers[idx],
+                            cell,
+                            sanitized
+                        );
+                    }
+                    continue;
+                }
                 if sanitize_text_in_place(cell) {
                     sanitized_cells += 1;
                 }
             }
             if sanitized_cells > 0 {
                 stats.cells_modified += sanitized_cells;
+                stats.cells_sanitized += sanitized_cells;
             }
 
             if let Some((title_idx, title_name)) = title_column {
@@ -298,8 +947,27 @@ impl CsvModifier {
                 }
             }
 
-            for (column_name, modifier) in &self.column_modifiers {
+            // Compare before modifiers run: the file-extension modifier rewrites the suffix.
+            let extension_mismatch = file_idx.and_then(|idx| {
+                let file_value = normalize_cell(row_values.get(idx)?);
+                let declared = extension_idxs
+                    .iter()
+                    .filter_map(|&idx| row_values.get(idx))
+                    .map(|value| normalize_cell(value))
+                    .find(|value| !value.is_empty())?;
+                file_suffix_mismatch(file_value, declared)
+                    .map(|suffix| (file_value.to_string(), suffix.to_string(), declared.to_string()))
+            });
+
+            let modifier_steps = self
+                .column_modifiers
+                .iter()
+                .flat_map(|(column, chain)| chain.iter().map(move |modifier| (column, modifier)));
+            for (column_name, modifier) in modifier_steps {
                 if let Some(&col_index) = header_map.get(column_name) {
+                    if is_protected(col_index) && !modifier.runs_on_protected_columns() {
+                        continue;
+                    }
                     let mut post_update: Option<(usize, String)> = None;
                     let mut clear_cell = false;
                     let mut invalidate_row = false;
@@ -317,8 +985,15 @@ impl CsvModifier {
                             if column_name.as_str() == "accessIdentifier" {
                                 let normalized_value = normalize_cell(cell.as_str());
                                 if !normalized_value.is_empty() {
-                                    if seen_access_identifiers.contains(normalized_value) {
+                                    if seen_access_identifiers.get(normalized_value)?.is_some() {
                                         stats.validation_failures += 1;
+                                        if self.dedup_kept_output.is_some()
+                                            && !duplicated_access_identifiers
+                                                .contains(normalized_value)
+                                        {
+                                            duplicated_access_identifiers
+                                                .insert(normalized_value.to_string());
+                                        }
 
                                         if stats.validation_failures <= 25 {
                                             warn!(
@@ -347,8 +1022,28 @@ impl CsvModifier {
                                 let original = cell.clone();
                                 let new_value = modifier.modify(cell, &row_context);
 
-                                if original != new_value {
+                                if original != new_value && is_protected(col_index) {
+                                    info!(
+                                        target: TRANSFORM_LOG_TARGET,
+                                        "Row {}: protected column '{}' not changed '{}' -> '{}' by '{}'",
+                                        row_idx + 1,
+                                        column_name,
+                                        original,
+                                        new_value,
+                                        modifier.description()
+                                    );
+                                } else if original != new_value {
+                                    debug!(
+                                        target: TRANSFORM_LOG_TARGET,
+                                        "Row {}: column '{}' changed '{}' -> '{}' by '{}'",
+                                        row_idx + 1,
+                                        column_name,
+                                        original,
+                                        new_value,
+                                        modifier.description()
+                                    );
                                     stats.cells_modified += 1;
+                                    stats.cells_modified_by_modifiers += 1;
                                     post_update = Some((col_index, new_value));
                                 }
                             }
@@ -384,7 +1079,7 @@ impl CsvModifier {
 
                             if sanitized_cell.is_empty()
                                 && !original_cell_value.trim().is_empty()
-                                && column_name.as_str() == "parent_id"
+                                && *column_name == self.parent_id_column
                             {
                                 clear_cell = true;
                             }
@@ -419,7 +1114,11 @@ impl CsvModifier {
                                     missing_fields.push("accessIdentifier");
                                 }
 
-                                let reason = if missing_fields.is_empty() {
+                                let reason = if let Some(reason) =
+                                    modifier.validation_reason(&original_cell_value, &row_context)
+                                {
+                                    reason
+                                } else if missing_fields.is_empty() {
                                     "validation predicate returned false without missing fields"
                                         .to_string()
                                 } else {
@@ -460,11 +1159,19 @@ impl CsvModifier {
                         if let Some(cell_mut) = row_values.get_mut(target_col) {
                             *cell_mut = new_value;
                         }
+                    } else if clear_cell && is_protected(col_index) {
+                        info!(
+                            target: TRANSFORM_LOG_TARGET,
+                            "Row {}: protected column '{}' not cleared",
+                            row_idx + 1,
+                            column_name
+                        );
                     } else if clear_cell {
                         if let Some(cell_mut) = row_values.get_mut(col_index) {
                             if !cell_mut.is_empty() {
                                 cell_mut.clear();
                                 stats.cells_modified += 1;
+                                stats.cells_modified_by_modifiers += 1;
                             }
                         }
                     }
@@ -479,43 +1186,148 @@ impl CsvModifier {
                 continue;
             }
 
+            if let Some((file_value, suffix, declared)) = extension_mismatch {
+                stats.extension_mismatches += 1;
+                if stats.extension_mismatches <= 25 {
+                    warn!(
+                        "File extension mismatch at row {}: file '{}' has suffix '{}' but file_extension is '{}'.",
+                        row_idx + 1,
+                        file_value,
+                        suffix,
+                        declared
+                    );
+                } else if stats.extension_mismatches == 26 {
+                    warn!(
+                        "More than 25 file extension mismatches encountered. Suppressing additional mismatch logs."
+                    );
+                }
+            }
+
             for (idx, cell) in row_values.iter_mut().enumerate() {
                 let header_name = headers.get(idx).map(|s| s.as_str()).unwrap_or("");
-                if header_name.eq_ignore_ascii_case("field_description")
-                    || header_name.eq_ignore_ascii_case("description")
-                {
+                if keeps_semicolons(header_name) {
                     continue;
                 }
 
-                if cell.contains(';') {
+                if cell.contains(';') && is_protected(idx) {
+                    info!(
+                        target: TRANSFORM_LOG_TARGET,
+                        "Row {}: protected column '{}' keeps ';' in '{}'",
+                        row_idx + 1,
+                        header_name,
+                        cell
+                    );
+                } else if cell.contains(';') {
                     *cell = cell.replace(';', "|");
                     stats.cells_modified += 1;
+                    stats.cells_subdelimiter_replaced += 1;
                 }
             }
 
             if let Some(identifier) = current_access_identifier {
-                seen_access_identifiers.insert(identifier);
+                seen_access_identifiers.insert(identifier, row_idx + 1)?;
+            }
+
+            if let Some(crosswalk) = crosswalk_writer.as_mut() {
+                let cell = |idx: Option<usize>| {
+                    idx.and_then(|i| row_values.get(i))
+                        .map_or("", |value| value.as_str())
+                };
+                crosswalk.write_record([
+                    (row_idx + 1).to_string().as_str(),
+                    access_identifier_idx.and_then(|i| record.get(i)).unwrap_or(""),
+                    cell(crosswalk_parent_idx),
+                    cell(file_idx),
+                ])?;
             }
 
-            writer.write_record(&row_values)?;
+            if self.canonical {
+                canonical_rows.push((row_idx, row_values));
+            } else {
+                writer.write_row(&row_values)?;
+                if self.verify_passthrough {
+                    written_rows.push(row_idx);
+                }
+            }
             stats.total_rows += 1;
         }
 
+        if self.canonical {
+            if let Some(&key_idx) = header_map.get("accessIdentifier") {
+                canonical_rows.sort_by(|(_, a), (_, b)| a[key_idx].cmp(&b[key_idx]));
+            }
+            for (source_row, row) in &canonical_rows {
+                writer.write_row(row)?;
+                if self.verify_passthrough {
+                    written_rows.push(*source_row);
+                }
+            }
+        }
+
         for column_name in self.column_modifiers.keys() {
             stats.columns_processed.insert(column_name.clone());
         }
 
         writer.flush()?;
-        Ok(stats)
+        if let Some(crosswalk) = crosswalk_writer.as_mut() {
+            crosswalk.flush()?;
+        }
+
+        if self.strict_extensions && stats.extension_mismatches > 0 {
+            // Rows were streamed to disk as they were processed; remove them so a failed run
+            // leaves no output that looks complete.
+            drop(writer);
+            drop(crosswalk_writer);
+            for path in std::iter::once(output_path).chain(self.crosswalk_output.as_deref()) {
+                if let Err(err) = std::fs::remove_file(path) {
+                    warn!("Could not remove {} after the strict check failed: {}", path, err);
+                }
+            }
+            anyhow::bail!(
+                "{} row(s) have a file suffix that differs from file_extension (see warnings above); failing in strict mode",
+                stats.extension_mismatches
+            );
+        }
+
+        if let Some(path) = &self.dedup_kept_output {
+            let mut kept: Vec<(&str, usize)> = Vec::with_capacity(duplicated_access_identifiers.len());
+            for identifier in &duplicated_access_identifiers {
+                if let Some(row_number) = seen_access_identifiers.get(identifier)? {
+                    kept.push((identifier.as_str(), row_number));
+                }
+            }
+            kept.sort_by_key(|&(_, row_number)| row_number);
+
+            let kept_file = File::create(path).context("Failed to create dedup kept output file")?;
+            let mut kept_writer = Writer::from_writer(kept_file);
+            kept_writer.write_record(["accessIdentifier", "kept_row_number"])?;
+            for (identifier, row_number) in kept {
+                kept_writer.write_record([identifier, row_number.to_string().as_str()])?;
+            }
+            kept_writer.flush()?;
+        }
+
+        Ok((stats, written_rows))
     }
 }
 
 #[derive(Debug, Default)]
 pub struct ProcessingStats {
     pub total_rows: usize,
+    /// Total cells changed; the sum of the three counters below.
     pub cells_modified: usize,
+    /// Cells cleaned of NBSPs / mojibake.
+    pub cells_sanitized: usize,
+    /// Cells changed by column modifiers (including cleared `parent_id` / `file` cells).
+    pub cells_modified_by_modifiers: usize,
+    /// Cells whose `;` were replaced with the `|` subdelimiter.
+    pub cells_subdelimiter_replaced: usize,
     pub validation_failures: usize,
+    /// Written rows whose input `file` suffix differs from the declared `file_extension`.
+    pub extension_mismatches: usize,
     pub skipped_rows: usize, // Track skipped rows
+    /// Rows whose title was rejoined after an unquoted comma split it (`with_unquoted_comma_repair`).
+    pub unquoted_comma_repairs: usize,
     pub columns_processed: std::collections::HashSet<String>,
 }
 
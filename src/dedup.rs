@@ -0,0 +1,166 @@
+use anyhow::{Context, Result};
+use log::info;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::hash::{Hash, Hasher};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use tempfile::TempDir;
+
+/// Number of on-disk bucket files a spilled index is split across.
+const SPILL_BUCKETS: u64 = 64;
+
+/// Keys seen so far during deduplication, with the row number each was first kept at.
+///
+/// Without a cap every key stays in memory. With a cap, once more than `cap` keys are held they
+/// are moved to bucket files in a temporary directory and only a 64-bit hash per spilled key is
+/// kept in memory. A lookup reads a bucket file only when the hash matches, so results stay
+/// exact; a run with many duplicates of spilled keys pays one bucket scan per duplicate.
+pub(crate) struct SeenKeys {
+    memory: HashMap<String, usize>,
+    cap: Option<usize>,
+    spill: Option<Spill>,
+}
+
+struct Spill {
+    dir: TempDir,
+    buckets: Vec<BufWriter<File>>,
+    hashes: HashSet<u64>,
+}
+
+fn key_hash(key: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl SeenKeys {
+    /// `cap` limits how many full keys are held in memory; `None` keeps them all.
+    pub(crate) fn new(cap: Option<usize>) -> Self {
+        Self {
+            memory: HashMap::with_capacity(cap.unwrap_or(1024).min(1024)),
+            cap,
+            spill: None,
+        }
+    }
+
+    /// Row number `key` was first recorded with, if it has been seen.
+    pub(crate) fn get(&mut self, key: &str) -> Result<Option<usize>> {
+        if let Some(&row) = self.memory.get(key) {
+            return Ok(Some(row));
+        }
+        let Some(spill) = self.spill.as_mut() else {
+            return Ok(None);
+        };
+        let hash = key_hash(key);
+        if !spill.hashes.contains(&hash) {
+            return Ok(None);
+        }
+        spill.find(hash, key)
+    }
+
+    /// Records `key` as first seen at `row`. The key must not have been recorded before.
+    pub(crate) fn insert(&mut self, key: String, row: usize) -> Result<()> {
+        self.memory.insert(key, row);
+        if self.cap.is_some_and(|cap| self.memory.len() > cap) {
+            self.spill_memory()?;
+        }
+        Ok(())
+    }
+
+    fn spill_memory(&mut self) -> Result<()> {
+        let spill = match self.spill.as_mut() {
+            Some(spill) => spill,
+            None => {
+                info!(
+                    "Dedup index exceeded {} keys; spilling seen accessIdentifiers to disk.",
+                    self.cap.unwrap_or_default()
+                );
+                self.spill.insert(Spill::create()?)
+            }
+        };
+        for (key, row) in self.memory.drain() {
+            spill.write(&key, row)?;
+        }
+        Ok(())
+    }
+}
+
+impl Spill {
+    fn create() -> Result<Self> {
+        let dir = tempfile::tempdir().context("Failed to create dedup spill directory")?;
+        let buckets = (0..SPILL_BUCKETS)
+            .map(|bucket| {
+                File::create(dir.path().join(format!("{}.idx", bucket)))
+                    .map(BufWriter::new)
+                    .context("Failed to create dedup spill file")
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            dir,
+            buckets,
+            hashes: HashSet::new(),
+        })
+    }
+
+    /// Appends `row<TAB>json-encoded key` to the key's bucket.
+    fn write(&mut self, key: &str, row: usize) -> Result<()> {
+        let hash = key_hash(key);
+        let bucket = &mut self.buckets[(hash % SPILL_BUCKETS) as usize];
+        writeln!(bucket, "{}\t{}", row, serde_json::to_string(key)?)
+            .context("Failed to write dedup spill file")?;
+        self.hashes.insert(hash);
+        Ok(())
+    }
+
+    fn find(&mut self, hash: u64, key: &str) -> Result<Option<usize>> {
+        let bucket = hash % SPILL_BUCKETS;
+        self.buckets[bucket as usize]
+            .flush()
+            .context("Failed to write dedup spill file")?;
+        let file = File::open(self.dir.path().join(format!("{}.idx", bucket)))
+            .context("Failed to read dedup spill file")?;
+
+        for line in BufReader::new(file).lines() {
+            let line = line.context("Failed to read dedup spill file")?;
+            let Some((row, stored)) = line.split_once('\t') else {
+                continue;
+            };
+            if serde_json::from_str::<String>(stored)? == key {
+                return Ok(Some(row.parse().context("Corrupt dedup spill file")?));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spilled_keys_are_found_exactly() {
+        let mut seen = SeenKeys::new(Some(2));
+        let keys = ["a", "b", "c\twith tab", "d\nwith newline", "e"];
+        for (row, key) in keys.iter().enumerate() {
+            seen.insert(key.to_string(), row + 1).unwrap();
+        }
+
+        assert!(seen.spill.is_some());
+        for (row, key) in keys.iter().enumerate() {
+            assert_eq!(seen.get(key).unwrap(), Some(row + 1), "{:?}", key);
+        }
+        assert_eq!(seen.get("missing").unwrap(), None);
+    }
+
+    #[test]
+    fn uncapped_index_never_spills() {
+        let mut seen = SeenKeys::new(None);
+        for row in 0..100 {
+            seen.insert(format!("key{}", row), row).unwrap();
+        }
+
+        assert!(seen.spill.is_none());
+        assert_eq!(seen.get("key42").unwrap(), Some(42));
+    }
+}
@@ -1,7 +1,10 @@
 use crate::csv_modifier::{CsvModifier, ProcessingStats};
 use anyhow::{Context, Result};
-use csv::Reader;
+use csv::{Reader, Writer};
 use std::io::Cursor;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
 
 fn is_valid_sheet_id(id: &str) -> bool {
     if id.len() < 2 || id == "edit" {
@@ -21,9 +24,99 @@ fn is_valid_sheet_id(id: &str) -> bool {
         .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
 }
 
+/// Collects worksheet gids, in order of first appearance, from a spreadsheet's `htmlview` page.
+///
+/// Only the sheet menu is read: tabs appear there as `sheet-button-<gid>` list items linking to
+/// `#gid=<gid>`. Other `gid=` occurrences on the page (script and resource URLs) are ignored.
+pub(crate) fn extract_sheet_gids(html: &str) -> Vec<String> {
+    let mut gids: Vec<String> = Vec::new();
+
+    for marker in ["sheet-button-", "#gid="] {
+        let mut offset = 0;
+        while let Some(pos) = html[offset..].find(marker) {
+            let start = offset + pos;
+            offset = start + marker.len();
+            // `sheet-button-` must be a whole id, not the tail of a longer name.
+            let attached = html[..start]
+                .chars()
+                .next_back()
+                .is_some_and(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'));
+            if attached {
+                continue;
+            }
+            let digits: String = html[offset..]
+                .chars()
+                .take_while(|c| c.is_ascii_digit())
+                .collect();
+            if !digits.is_empty() && !gids.contains(&digits) {
+                gids.push(digits);
+            }
+        }
+    }
+
+    gids
+}
+
+fn fetch_text(url: &str) -> Result<String> {
+    let response = reqwest::blocking::get(url)
+        .with_context(|| format!("Failed to fetch Google Sheets data from: {}", url))?;
+
+    if !response.status().is_success() {
+        anyhow::bail!(
+            "HTTP error {}: Failed to fetch Google Sheets data",
+            response.status()
+        );
+    }
+
+    response
+        .text()
+        .context("Failed to read response body as text")
+}
+
+/// Runs `fetch` over `items` on up to `concurrency` threads, returning results in input order.
+///
+/// A `concurrency` of 0 or 1 fetches sequentially on the calling thread.
+pub(crate) fn fetch_concurrently<T, F>(
+    items: &[T],
+    concurrency: usize,
+    fetch: F,
+) -> Vec<Result<String>>
+where
+    T: Sync,
+    F: Fn(&T) -> Result<String> + Sync,
+{
+    if concurrency <= 1 || items.len() <= 1 {
+        return items.iter().map(&fetch).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<Result<String>>>> =
+        Mutex::new(items.iter().map(|_| None).collect());
+
+    thread::scope(|scope| {
+        for _ in 0..concurrency.min(items.len()) {
+            scope.spawn(|| loop {
+                let idx = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(idx) else {
+                    break;
+                };
+                let result = fetch(item);
+                results.lock().unwrap_or_else(|e| e.into_inner())[idx] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap_or_else(|e| e.into_inner())
+        .into_iter()
+        .map(|result| result.unwrap_or_else(|| Err(anyhow::anyhow!("Fetch did not complete"))))
+        .collect()
+}
+
 impl CsvModifier {
-    /// Convert Google Sheets URL to CSV export URL
-    pub fn google_sheets_to_csv_url(url: &str) -> Result<String> {
+    /// Extract the spreadsheet ID from a Google Sheets URL
+    pub fn google_sheets_id(url: &str) -> Result<String> {
         let url = url::Url::parse(url).context("Invalid Google Sheets URL")?;
 
         if url.host_str() != Some("docs.google.com") {
@@ -36,46 +129,131 @@ impl CsvModifier {
         let path = url.path();
         if let Some(start) = path.find("/spreadsheets/d/") {
             let id_start = start + 16;
-            if let Some(end) = path[id_start..].find('/') {
-                let sheet_id = &path[id_start..id_start + end];
-                if sheet_id.is_empty() || !is_valid_sheet_id(sheet_id) {
-                    anyhow::bail!("Invalid or empty spreadsheet ID in URL: {}", url);
-                }
-                return Ok(format!(
-                    "https://docs.google.com/spreadsheets/d/{}/export?format=csv",
-                    sheet_id
-                ));
-            } else {
-                let sheet_id = &path[id_start..];
-                if sheet_id.is_empty() || !is_valid_sheet_id(sheet_id) {
-                    anyhow::bail!("Invalid or empty spreadsheet ID in URL: {}", url);
-                }
-                return Ok(format!(
-                    "https://docs.google.com/spreadsheets/d/{}/export?format=csv",
-                    sheet_id
-                ));
+            let sheet_id = match path[id_start..].find('/') {
+                Some(end) => &path[id_start..id_start + end],
+                None => &path[id_start..],
+            };
+            if sheet_id.is_empty() || !is_valid_sheet_id(sheet_id) {
+                anyhow::bail!("Invalid or empty spreadsheet ID in URL: {}", url);
             }
+            return Ok(sheet_id.to_string());
         }
 
         anyhow::bail!("Could not extract spreadsheet ID from URL - path should contain '/spreadsheets/d/': {}", url)
     }
 
+    /// Convert Google Sheets URL to CSV export URL
+    pub fn google_sheets_to_csv_url(url: &str) -> Result<String> {
+        Ok(format!(
+            "https://docs.google.com/spreadsheets/d/{}/export?format=csv",
+            Self::google_sheets_id(url)?
+        ))
+    }
+
+    /// Convert Google Sheets URL to the CSV export URL of a specific worksheet (tab)
+    pub fn google_sheets_to_csv_url_for_gid(url: &str, gid: &str) -> Result<String> {
+        if gid.is_empty() || !gid.chars().all(|c| c.is_ascii_digit()) {
+            anyhow::bail!("Invalid worksheet gid '{}': expected digits only", gid);
+        }
+        Ok(format!("{}&gid={}", Self::google_sheets_to_csv_url(url)?, gid))
+    }
+
     pub fn fetch_google_sheets_csv(url: &str) -> Result<String> {
         let csv_url = Self::google_sheets_to_csv_url(url)?;
+        fetch_text(&csv_url)
+    }
 
-        let response = reqwest::blocking::get(&csv_url)
-            .with_context(|| format!("Failed to fetch Google Sheets CSV from: {}", csv_url))?;
+    /// Fetch several spreadsheets' CSV exports, up to `concurrency` at a time.
+    ///
+    /// Results are returned in the order of `urls`, one per URL, so callers can process them
+    /// sequentially afterwards.
+    pub fn fetch_google_sheets_csv_batch(
+        urls: &[String],
+        concurrency: usize,
+    ) -> Vec<Result<String>> {
+        fetch_concurrently(urls, concurrency, |url| Self::fetch_google_sheets_csv(url))
+    }
+
+    /// Fetch every worksheet of the spreadsheet and concatenate them into one CSV.
+    ///
+    /// Tabs are discovered from the spreadsheet's `htmlview` page, so the sheet must be viewable
+    /// by link. All tabs must share the first tab's header row.
+    pub fn fetch_google_sheets_all_tabs_csv(url: &str) -> Result<String> {
+        Self::fetch_google_sheets_all_tabs_csv_with_concurrency(url, 1)
+    }
 
-        if !response.status().is_success() {
+    /// Like [`Self::fetch_google_sheets_all_tabs_csv`], fetching up to `concurrency` tabs at a time.
+    pub fn fetch_google_sheets_all_tabs_csv_with_concurrency(
+        url: &str,
+        concurrency: usize,
+    ) -> Result<String> {
+        let export_url = Self::google_sheets_to_csv_url(url)?;
+        let htmlview_url = export_url.replace("/export?format=csv", "/htmlview");
+        let html = fetch_text(&htmlview_url)?;
+
+        let gids = extract_sheet_gids(&html);
+        if gids.is_empty() {
             anyhow::bail!(
-                "HTTP error {}: Failed to fetch Google Sheets data",
-                response.status()
+                "Could not find any worksheet tabs at {}; check that the sheet is shared by link",
+                htmlview_url
             );
         }
 
-        response
-            .text()
-            .context("Failed to read response body as text")
+        let fetched = fetch_concurrently(&gids, concurrency, |gid| {
+            let csv_url = Self::google_sheets_to_csv_url_for_gid(url, gid)?;
+            fetch_text(&csv_url).with_context(|| format!("Failed to fetch worksheet gid={}", gid))
+        });
+
+        let mut tabs = Vec::with_capacity(gids.len());
+        for (gid, csv_data) in gids.into_iter().zip(fetched) {
+            tabs.push((gid, csv_data?));
+        }
+
+        Self::concatenate_sheet_tabs(&tabs)
+    }
+
+    /// Concatenate `(gid, csv)` tabs into one CSV, keeping the first tab's header row.
+    ///
+    /// Errors if any tab's header row differs from the first tab's.
+    pub fn concatenate_sheet_tabs(tabs: &[(String, String)]) -> Result<String> {
+        let mut writer = Writer::from_writer(Vec::new());
+        let mut expected_headers: Option<(String, csv::StringRecord)> = None;
+
+        for (gid, csv_data) in tabs {
+            let mut reader = Reader::from_reader(Cursor::new(csv_data.as_bytes()));
+            let headers = reader
+                .headers()
+                .with_context(|| format!("Failed to read headers of worksheet gid={}", gid))?
+                .clone();
+
+            match &expected_headers {
+                None => {
+                    writer.write_record(&headers)?;
+                    expected_headers = Some((gid.clone(), headers));
+                }
+                Some((first_gid, first_headers)) if *first_headers != headers => {
+                    anyhow::bail!(
+                        "Worksheet gid={} headers [{}] do not match worksheet gid={} headers [{}]",
+                        gid,
+                        headers.iter().collect::<Vec<_>>().join(", "),
+                        first_gid,
+                        first_headers.iter().collect::<Vec<_>>().join(", ")
+                    );
+                }
+                Some(_) => {}
+            }
+
+            for record in reader.records() {
+                let record =
+                    record.with_context(|| format!("Failed to read worksheet gid={}", gid))?;
+                writer.write_record(&record)?;
+            }
+        }
+
+        let bytes = writer
+            .into_inner()
+            .map_err(|err| anyhow::anyhow!("Failed to finish concatenated CSV: {}", err))?;
+        String::from_utf8(bytes).context("Concatenated CSV is not valid UTF-8")
     }
 
     /// Process CSV data from Google Sheets URL and write to output file
@@ -85,7 +263,100 @@ impl CsvModifier {
         output_path: &str,
     ) -> Result<ProcessingStats> {
         let csv_data = Self::fetch_google_sheets_csv(sheets_url)?;
-        let mut reader = Reader::from_reader(Cursor::new(csv_data));
-        self.process_csv_reader(&mut reader, output_path)
+        self.process_csv_data(&csv_data, output_path)
+    }
+
+    /// Process a single worksheet (tab) of a Google Sheets workbook, selected by its `gid`
+    pub fn process_google_sheets_tab(
+        &self,
+        sheets_url: &str,
+        gid: &str,
+        output_path: &str,
+    ) -> Result<ProcessingStats> {
+        let csv_url = Self::google_sheets_to_csv_url_for_gid(sheets_url, gid)?;
+        let csv_data = fetch_text(&csv_url)?;
+        self.process_csv_data(&csv_data, output_path)
+    }
+
+    /// Process every worksheet of a Google Sheets workbook as one concatenated CSV, fetching up
+    /// to `concurrency` tabs at a time (0 or 1 is sequential)
+    pub fn process_google_sheets_all_tabs(
+        &self,
+        sheets_url: &str,
+        concurrency: usize,
+        output_path: &str,
+    ) -> Result<ProcessingStats> {
+        let csv_data =
+            Self::fetch_google_sheets_all_tabs_csv_with_concurrency(sheets_url, concurrency)?;
+        self.process_csv_data(&csv_data, output_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_gids_in_order_without_duplicates() {
+        let html = r##"<ul id="sheet-menu"><li id="sheet-button-0"><a href="#gid=0">Box 1</a></li>
+<li id="sheet-button-1843120417"><a href="#gid=1843120417">Box 2</a></li>
+<li id="sheet-button-77"><a href="#gid=77">Box 3</a></li></ul>"##;
+
+        assert_eq!(extract_sheet_gids(html), vec!["0", "1843120417", "77"]);
+    }
+
+    #[test]
+    fn ignores_gids_outside_the_sheet_menu() {
+        let html = r##"<script src="https://docs.google.com/static/client.js?gid=999&xgid=555"></script>
+<link rel="stylesheet" href="/resource?id=abc&gid=123">
+<div id="other-sheet-button-42"></div>
+<ul id="sheet-menu"><li id="sheet-button-0"><a href="#gid=0">Box 1</a></li>
+<li id="sheet-button-7"><a href="#gid=7">Box 2</a></li></ul>"##;
+
+        assert_eq!(extract_sheet_gids(html), vec!["0", "7"]);
+    }
+
+    #[test]
+    fn extracts_nothing_from_unrelated_html() {
+        assert!(extract_sheet_gids("<html><body>Sign in</body></html>").is_empty());
+    }
+
+    #[test]
+    fn concurrent_fetch_returns_every_result_in_order() {
+        let urls: Vec<String> = (0..20).map(|i| format!("https://example.test/{}", i)).collect();
+        let calls = AtomicUsize::new(0);
+
+        let results = fetch_concurrently(&urls, 4, |url| {
+            calls.fetch_add(1, Ordering::Relaxed);
+            thread::sleep(std::time::Duration::from_millis(5));
+            if url.ends_with("/13") {
+                anyhow::bail!("stub failure for {}", url);
+            }
+            Ok(format!("body of {}", url))
+        });
+
+        assert_eq!(calls.load(Ordering::Relaxed), urls.len());
+        assert_eq!(results.len(), urls.len());
+        for (url, result) in urls.iter().zip(&results) {
+            if url.ends_with("/13") {
+                assert!(result.is_err());
+            } else {
+                assert_eq!(result.as_ref().unwrap(), &format!("body of {}", url));
+            }
+        }
+    }
+
+    #[test]
+    fn sequential_fetch_is_used_for_concurrency_of_one() {
+        let items = vec!["a".to_string(), "b".to_string()];
+        let main_thread = thread::current().id();
+
+        let results = fetch_concurrently(&items, 1, |item| {
+            assert_eq!(thread::current().id(), main_thread);
+            Ok(item.to_uppercase())
+        });
+
+        let bodies: Vec<String> = results.into_iter().map(|r| r.unwrap()).collect();
+        assert_eq!(bodies, vec!["A", "B"]);
     }
 }
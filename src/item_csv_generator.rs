@@ -1,7 +1,12 @@
+use crate::modifiers::{is_container_identifier, DEFAULT_PARENT_ID_COLUMN};
 use anyhow::{Context, Result};
+use clap::ValueEnum;
 use csv::{Reader, Writer};
-use std::collections::HashMap;
-use std::fs::File;
+use log::{error, warn};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::{File, OpenOptions};
+use std::io::ErrorKind;
+use std::path::Path;
 
 fn normalize_cell(value: &str) -> &str {
     let trimmed = value.trim();
@@ -16,14 +21,95 @@ fn is_effectively_empty(value: &str) -> bool {
     normalize_cell(value).is_empty()
 }
 
+/// How season and quarter dates such as `Spring 2019` or `Q2 2019` are represented.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum SeasonConvention {
+    /// Representative month: Spring→03, Summer→06, Autumn/Fall→09, Winter→12,
+    /// Q1→01, Q2→04, Q3→07, Q4→10 (written as `MM/YYYY`)
+    #[default]
+    Month,
+    /// EDTF sub-year codes: Spring→21, Summer→22, Autumn/Fall→23, Winter→24 (level 1),
+    /// Q1→33 … Q4→36 (level 2), written as `YYYY-SS`
+    Edtf,
+}
+
+impl SeasonConvention {
+    fn code_for(self, word: &str) -> Option<u8> {
+        let word = word.to_ascii_lowercase();
+        let (month, edtf) = match word.as_str() {
+            "spring" => (3, 21),
+            "summer" => (6, 22),
+            "autumn" | "fall" => (9, 23),
+            "winter" => (12, 24),
+            "q1" => (1, 33),
+            "q2" => (4, 34),
+            "q3" => (7, 35),
+            "q4" => (10, 36),
+            _ => return None,
+        };
+        Some(match self {
+            SeasonConvention::Month => month,
+            SeasonConvention::Edtf => edtf,
+        })
+    }
+}
+
+/// How a group's mean year is rounded when no single month dominates its dates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum DateAverageRounding {
+    /// Round to the nearest year (halves round up)
+    #[default]
+    Nearest,
+    /// Round down, never claiming a later year than the mean
+    Floor,
+    /// Round up
+    Ceil,
+}
+
+/// Mean of the observed years (weighted by occurrences), rounded per `rounding` and kept within
+/// the earliest and latest observed year. `None` when no years were observed.
+fn average_year(year_counts: &HashMap<u16, usize>, rounding: DateAverageRounding) -> Option<u16> {
+    let (sum, total) = year_counts
+        .iter()
+        .fold((0u64, 0u64), |(s, t), (&year, &count)| {
+            (s + year as u64 * count as u64, t + count as u64)
+        });
+    if total == 0 {
+        return None;
+    }
+
+    let mean = sum as f64 / total as f64;
+    let rounded = match rounding {
+        DateAverageRounding::Nearest => mean.round(),
+        DateAverageRounding::Floor => mean.floor(),
+        DateAverageRounding::Ceil => mean.ceil(),
+    } as u16;
+
+    let min_year = year_counts.keys().copied().min()?;
+    let max_year = year_counts.keys().copied().max()?;
+    Some(rounded.clamp(min_year, max_year))
+}
+
+/// Formats a (year, month-or-EDTF-code) pair for `field_edtf_date`.
+fn format_year_month(year: u16, month: u8) -> String {
+    if month > 12 {
+        // EDTF season/quarter code
+        format!("{}-{}", year, month)
+    } else {
+        format!("{:02}/{}", month, year)
+    }
+}
+
 /// Attempt to extract a (year, optional month) from a free-form date string.
 /// Heuristics (no external crates):
 /// - Find first 4-digit year (1000..=2999)
 /// - Prefer month adjacent to the year with '-' or '/' as delimiter
 ///   - After the year (YYYY[-/]MM)
 ///   - Or before the year (MM[-/]YYYY)
-/// - If no adjacent month found, returns (year, None)
-fn parse_year_and_month(value: &str) -> Option<(u16, Option<u8>)> {
+/// - Otherwise a season or quarter word right before the year (`Spring 2019`, `Q2 2019`)
+///   maps to a month or EDTF code per `seasons`
+/// - If none of these is found, returns (year, None)
+fn parse_year_and_month(value: &str, seasons: SeasonConvention) -> Option<(u16, Option<u8>)> {
     let s = value.trim();
     if s.is_empty() {
         return None;
@@ -94,14 +180,288 @@ fn parse_year_and_month(value: &str) -> Option<(u16, Option<u8>)> {
         }
     }
 
+    // Try a season/quarter word before the year: "Spring 2019", "Q2, 2019"
+    let before = s[..y_idx].trim_end_matches(|c: char| c.is_whitespace() || c == ',');
+    let word_start = before
+        .char_indices()
+        .rev()
+        .find(|(_, c)| !c.is_ascii_alphanumeric())
+        .map(|(pos, c)| pos + c.len_utf8())
+        .unwrap_or(0);
+    if let Some(code) = seasons.code_for(&before[word_start..]) {
+        return Some((year, Some(code)));
+    }
+
     Some((year, None))
 }
 
 #[derive(Debug, Default, PartialEq, Eq)]
 pub struct ItemGenerationStats {
+    /// Parents with rows in this input; with `append`, parents only in the existing file are
+    /// not included.
     pub unique_parents: usize,
     pub total_items: usize,
     pub skipped_rows: usize,
+    /// Container rows used as parent titles (with `promote_containers`). Container rows are not
+    /// items and are not counted in `total_items`; a parent with only a container row is skipped.
+    pub containers_promoted: usize,
+    /// Rows the items ledger shows have already been counted (with `ledger`).
+    pub ledger_skipped_rows: usize,
+    /// Rows not counted because they have no `accessIdentifier` to record (with `ledger`).
+    pub ledger_untracked_rows: usize,
+}
+
+/// What [`ItemCsvGenerator::generate_batch`] does when one input fails.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ItemErrorPolicy {
+    /// Stop at the first failing input
+    #[default]
+    Abort,
+    /// Record the failure, skip that input and continue with the rest
+    Continue,
+}
+
+/// Outcome of [`ItemCsvGenerator::generate_batch`].
+#[derive(Debug, Default)]
+pub struct ItemBatchReport {
+    /// `(input_path, output_path, stats)` for every items file written, in input order.
+    pub generated: Vec<(String, String, ItemGenerationStats)>,
+    /// `(input_path, error)` for every input skipped under [`ItemErrorPolicy::Continue`].
+    pub failed: Vec<(String, anyhow::Error)>,
+}
+
+/// A column whose values are collected per parent into an extra items column (`--items-aggregate`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemAggregate {
+    /// Source column; also the name of the added items column.
+    pub column: String,
+    /// Keep only the first occurrence of each value.
+    pub distinct: bool,
+}
+
+impl ItemAggregate {
+    /// Parses `column` or `column:distinct`.
+    pub fn parse(value: &str) -> Result<Self> {
+        let (column, distinct) = match value.rsplit_once(':') {
+            Some((column, "distinct")) => (column, true),
+            Some((_, modifier)) => anyhow::bail!(
+                "Invalid items aggregate '{}': unknown modifier '{}' (expected COLUMN or COLUMN:distinct)",
+                value,
+                modifier
+            ),
+            None => (value, false),
+        };
+        let column = column.trim();
+        if column.is_empty() {
+            anyhow::bail!("Invalid items aggregate '{}': column name is empty", value);
+        }
+        Ok(Self {
+            column: column.to_string(),
+            distinct,
+        })
+    }
+}
+
+/// Options for [`ItemCsvGenerator::generate_with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct ItemGenerationOptions {
+    /// Column holding an authoritative item count per row (e.g. from a physical inventory).
+    ///
+    /// When set and present in the input, `# of items` is the **sum** of this column across the
+    /// group's rows instead of the row count. Empty cells contribute nothing, non-numeric cells are
+    /// ignored with a warning, and a group in which no row carries a count falls back to its row
+    /// count. When the column is absent from the input, every group uses row counting.
+    pub count_column: Option<String>,
+    /// Column to group rows by (default `parent_id`), e.g. `compound_id` or `field_member_of`.
+    pub parent_id_column: Option<String>,
+    /// How `Spring 2019` / `Q2 2019` style dates contribute to `field_edtf_date`.
+    pub season_convention: SeasonConvention,
+    /// How the mean year is rounded when a group's `field_edtf_date` falls back to averaging.
+    pub date_average_rounding: DateAverageRounding,
+    /// Extra items columns holding each parent's values of these columns, split on and joined
+    /// with the `|` subdelimiter in first-seen row order.
+    pub aggregates: Vec<ItemAggregate>,
+    /// Treat container rows (`accessIdentifier` ending in `_00` / `_000`, kept by processing with
+    /// `--keep-containers`) as the parent itself: their `fileTitle` becomes the group title ahead of
+    /// item titles, and they are not counted as items.
+    pub promote_containers: bool,
+    /// Merge into an existing items file instead of overwriting it: counts of parents already in
+    /// the file are added to, other existing values are kept (filled in only where empty), and
+    /// new parents are added.
+    pub append: bool,
+    /// CSV of `accessIdentifier,parent_id` for every row that has contributed to the items file.
+    ///
+    /// Rows whose `accessIdentifier` is already listed are skipped, and newly counted rows are
+    /// appended once the items file is written, so re-running `append` over overlapping or
+    /// repeated inputs never counts a row twice. Requires an `accessIdentifier` column; rows
+    /// with an empty one cannot be recorded and are not counted.
+    pub ledger: Option<String>,
+}
+
+impl ItemGenerationOptions {
+    fn parent_id_column(&self) -> &str {
+        self.parent_id_column
+            .as_deref()
+            .unwrap_or(DEFAULT_PARENT_ID_COLUMN)
+    }
+}
+
+/// Source rows that have already contributed to an items file (`--items-ledger`).
+struct ItemLedger {
+    path: String,
+    seen: HashSet<String>,
+    pending: Vec<(String, String)>,
+}
+
+impl ItemLedger {
+    const HEADERS: [&'static str; 2] = ["accessIdentifier", "parent_id"];
+
+    /// Reads the ledger at `path`; a missing file is an empty ledger.
+    fn load(path: &str) -> Result<Self> {
+        let mut seen = HashSet::new();
+        match File::open(path) {
+            Ok(file) => {
+                let mut reader = Reader::from_reader(file);
+                let headers = reader
+                    .headers()
+                    .with_context(|| format!("Failed to read items ledger {}", path))?;
+                if !headers.is_empty() && headers.iter().ne(Self::HEADERS) {
+                    anyhow::bail!(
+                        "{} is not an items ledger: expected header '{}'",
+                        path,
+                        Self::HEADERS.join(",")
+                    );
+                }
+                for record in reader.records() {
+                    let record =
+                        record.with_context(|| format!("Failed to read items ledger {}", path))?;
+                    if let Some(access_identifier) = record.get(0) {
+                        seen.insert(normalize_cell(access_identifier).to_string());
+                    }
+                }
+            }
+            Err(err) if err.kind() == ErrorKind::NotFound => {}
+            Err(err) => {
+                return Err(err).with_context(|| format!("Failed to open items ledger {}", path));
+            }
+        }
+        Ok(Self {
+            path: path.to_string(),
+            seen,
+            pending: Vec::new(),
+        })
+    }
+
+    /// Records that `access_identifier` contributed to `parent_id`; `false` when it already had.
+    fn record(&mut self, access_identifier: &str, parent_id: &str) -> bool {
+        if !self.seen.insert(access_identifier.to_string()) {
+            return false;
+        }
+        self.pending
+            .push((access_identifier.to_string(), parent_id.to_string()));
+        true
+    }
+
+    /// Appends the rows recorded during this run to the ledger file.
+    fn save(self) -> Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let is_new = std::fs::metadata(&self.path).map_or(true, |meta| meta.len() == 0);
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open items ledger {}", self.path))?;
+        let mut writer = Writer::from_writer(file);
+        if is_new {
+            writer.write_record(Self::HEADERS)?;
+        }
+        for (access_identifier, parent_id) in &self.pending {
+            writer.write_record([access_identifier, parent_id])?;
+        }
+        writer
+            .flush()
+            .with_context(|| format!("Failed to write items ledger {}", self.path))?;
+        Ok(())
+    }
+}
+
+/// Position of `# of items` in an items row.
+const ITEMS_COUNT_IDX: usize = 2;
+/// Number of items columns before the `--items-aggregate` ones.
+const ITEMS_FIXED_COLUMNS: usize = 7;
+
+/// Rows of an existing items file keyed by `file_identifier`, for `append`.
+fn read_existing_items(
+    path: &str,
+    expected_headers: &[&str],
+) -> Result<BTreeMap<String, Vec<String>>> {
+    let mut rows = BTreeMap::new();
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(rows),
+        Err(err) => {
+            return Err(err).with_context(|| format!("Failed to open items file {}", path));
+        }
+    };
+    let mut reader = Reader::from_reader(file);
+    let headers = reader.headers()?;
+    if headers.iter().ne(expected_headers.iter().copied()) {
+        anyhow::bail!(
+            "Cannot append to {}: its columns differ from this run's ({}). \
+             Use the same --items-aggregate columns as the run that created it.",
+            path,
+            expected_headers.join(",")
+        );
+    }
+    for record in reader.records() {
+        let record = record.with_context(|| format!("Failed to read items file {}", path))?;
+        let row: Vec<String> = record.iter().map(str::to_string).collect();
+        rows.insert(row[0].clone(), row);
+    }
+    Ok(rows)
+}
+
+/// Folds a newly generated items row into the existing row for the same parent.
+fn merge_items_row(
+    existing: &mut [String],
+    new: Vec<String>,
+    aggregates: &[ItemAggregate],
+    path: &str,
+) -> Result<()> {
+    let parse_count = |value: &str| {
+        value.trim().parse::<u64>().with_context(|| {
+            format!(
+                "Cannot append to {}: '# of items' for '{}' is not a number ('{}')",
+                path, existing[0], value
+            )
+        })
+    };
+    let count = parse_count(&existing[ITEMS_COUNT_IDX])? + parse_count(&new[ITEMS_COUNT_IDX])?;
+
+    for (idx, value) in new.into_iter().enumerate() {
+        if idx == ITEMS_COUNT_IDX || value.is_empty() {
+            continue;
+        }
+        if let Some(aggregate) = idx
+            .checked_sub(ITEMS_FIXED_COLUMNS)
+            .and_then(|i| aggregates.get(i))
+        {
+            let mut values: Vec<&str> =
+                existing[idx].split('|').filter(|v| !v.is_empty()).collect();
+            for value in value.split('|') {
+                if !(aggregate.distinct && values.contains(&value)) {
+                    values.push(value);
+                }
+            }
+            existing[idx] = values.join("|");
+        } else if existing[idx].is_empty() {
+            existing[idx] = value;
+        }
+    }
+    existing[ITEMS_COUNT_IDX] = count.to_string();
+    Ok(())
 }
 
 pub struct ItemCsvGenerator;
@@ -111,6 +471,55 @@ impl ItemCsvGenerator {
         input_path: &str,
         output_path: &str,
         node: Option<&str>,
+    ) -> Result<ItemGenerationStats> {
+        Self::generate_with_options(input_path, output_path, node, &ItemGenerationOptions::default())
+    }
+
+    /// Generate an items file for each `(input_path, output_path)` job.
+    ///
+    /// With [`ItemErrorPolicy::Abort`] the first failure is returned as the error; with
+    /// [`ItemErrorPolicy::Continue`] it is logged, recorded in [`ItemBatchReport::failed`], and the
+    /// remaining jobs still run.
+    pub fn generate_batch(
+        jobs: &[(&str, &str)],
+        node: Option<&str>,
+        options: &ItemGenerationOptions,
+        policy: ItemErrorPolicy,
+    ) -> Result<ItemBatchReport> {
+        let mut report = ItemBatchReport::default();
+
+        for &(input_path, output_path) in jobs {
+            match Self::generate_with_options(input_path, output_path, node, options) {
+                Ok(stats) => {
+                    report.generated.push((
+                        input_path.to_string(),
+                        output_path.to_string(),
+                        stats,
+                    ));
+                }
+                Err(err) => match policy {
+                    ItemErrorPolicy::Abort => {
+                        return Err(err.context(format!(
+                            "Failed to generate items for {}",
+                            input_path
+                        )));
+                    }
+                    ItemErrorPolicy::Continue => {
+                        error!("Skipping {}: {:#}", input_path, err);
+                        report.failed.push((input_path.to_string(), err));
+                    }
+                },
+            }
+        }
+
+        Ok(report)
+    }
+
+    pub fn generate_with_options(
+        input_path: &str,
+        output_path: &str,
+        node: Option<&str>,
+        options: &ItemGenerationOptions,
     ) -> Result<ItemGenerationStats> {
         let file = File::open(input_path).context("Failed to open input file")?;
         let mut reader = Reader::from_reader(file);
@@ -118,29 +527,89 @@ impl ItemCsvGenerator {
         let headers = reader.headers()?.clone();
         let headers: Vec<String> = headers.iter().map(|h| h.to_string()).collect();
 
+        let parent_id_column = options.parent_id_column();
         let parent_id_idx = headers
             .iter()
-            .position(|h| h == "parent_id")
-            .context("Column 'parent_id' not found in CSV. Please ensure the input file has been processed with parent_id modifier.")?;
+            .position(|h| h == parent_id_column)
+            .with_context(|| format!("Column '{}' not found in CSV. Please ensure the input file has been processed with parent_id modifier.", parent_id_column))?;
         let file_title_idx = headers
             .iter()
             .position(|h| h == "fileTitle")
             .context("Column 'fileTitle' not found in CSV. Please ensure the input file contains a fileTitle column.")?;
         let field_date_idx_opt = headers.iter().position(|h| h == "field_date" || h == "field_edtf_date");
+        let access_identifier_idx = headers.iter().position(|h| h == "accessIdentifier");
+        let mut ledger = match options.ledger.as_deref() {
+            Some(path) => {
+                if access_identifier_idx.is_none() {
+                    anyhow::bail!(
+                        "Column 'accessIdentifier' not found in CSV; the items ledger needs it to recognise rows already counted."
+                    );
+                }
+                let ledger = ItemLedger::load(path)?;
+                let appending = options.append && Path::new(output_path).exists();
+                if !appending && !ledger.seen.is_empty() {
+                    warn!(
+                        "Items ledger {} lists {} rows but {} is not being appended to; those rows will not be counted.",
+                        path,
+                        ledger.seen.len(),
+                        output_path
+                    );
+                }
+                Some(ledger)
+            }
+            None => None,
+        };
+        let container_idx_opt = if options.promote_containers {
+            let idx = access_identifier_idx;
+            if idx.is_none() {
+                warn!("Column 'accessIdentifier' not found in CSV; no container rows can be promoted.");
+            }
+            idx
+        } else {
+            None
+        };
+        let count_idx_opt = options.count_column.as_deref().and_then(|column| {
+            let idx = headers.iter().position(|h| h == column);
+            if idx.is_none() {
+                warn!(
+                    "Count column '{}' not found in CSV; falling back to counting rows per parent.",
+                    column
+                );
+            }
+            idx
+        });
+        let aggregate_idxs: Vec<Option<usize>> = options
+            .aggregates
+            .iter()
+            .map(|aggregate| {
+                let idx = headers.iter().position(|h| *h == aggregate.column);
+                if idx.is_none() {
+                    warn!(
+                        "Aggregate column '{}' not found in CSV; its items column will be empty.",
+                        aggregate.column
+                    );
+                }
+                idx
+            })
+            .collect();
 
         #[derive(Default)]
         struct GroupData {
             title: String,
             count: usize,
+            external_count: Option<u64>,
             year_month_counts: HashMap<(u16, u8), usize>,
             year_counts: HashMap<u16, usize>,
             total_date_samples: usize,
+            aggregated: Vec<Vec<String>>,
         }
 
         let mut parent_data: HashMap<String, GroupData> = HashMap::with_capacity(256); // Pre-allocate
+        // Container rows per parent: the first non-empty title and how many rows there were.
+        let mut containers: HashMap<String, (Option<String>, usize)> = HashMap::new();
         let mut stats = ItemGenerationStats::default();
 
-        for result in reader.records() {
+        for (row_idx, result) in reader.records().enumerate() {
             let record = result?;
             stats.total_items += 1;
 
@@ -155,6 +624,40 @@ impl ItemCsvGenerator {
                 let parent_id_clean = normalize_cell(parent_id_raw);
                 let file_title_clean = normalize_cell(file_title_raw);
 
+                if let Some(ledger) = ledger.as_mut() {
+                    let access_identifier = access_identifier_idx
+                        .and_then(|idx| record.get(idx))
+                        .map_or("", normalize_cell);
+                    if access_identifier.is_empty() {
+                        // Such a row could never be recorded, so every rerun would count it again.
+                        stats.ledger_untracked_rows += 1;
+                        if stats.ledger_untracked_rows <= 25 {
+                            warn!(
+                                "Not counting row {} for parent '{}': it has no accessIdentifier to record in the items ledger.",
+                                row_idx + 1,
+                                parent_id_clean
+                            );
+                        }
+                        continue;
+                    }
+                    if !ledger.record(access_identifier, parent_id_clean) {
+                        stats.ledger_skipped_rows += 1;
+                        continue;
+                    }
+                }
+
+                let is_container = container_idx_opt
+                    .and_then(|idx| record.get(idx))
+                    .is_some_and(is_container_identifier);
+                if is_container {
+                    let (title, rows) = containers.entry(parent_id_clean.to_string()).or_default();
+                    if title.is_none() && !file_title_clean.is_empty() {
+                        *title = Some(file_title_clean.to_string());
+                    }
+                    *rows += 1;
+                    continue;
+                }
+
                 let entry = parent_data
                     .entry(parent_id_clean.to_string())
                     .or_default();
@@ -164,6 +667,45 @@ impl ItemCsvGenerator {
                 }
                 entry.count += 1;
 
+                if let Some(count_raw) = count_idx_opt.and_then(|idx| record.get(idx)) {
+                    let count_clean = normalize_cell(count_raw);
+                    if !count_clean.is_empty() {
+                        match count_clean.parse::<u64>() {
+                            Ok(n) => {
+                                *entry.external_count.get_or_insert(0) += n;
+                            }
+                            Err(_) => {
+                                warn!(
+                                    "Ignoring non-numeric item count '{}' at row {} for parent '{}'.",
+                                    count_clean,
+                                    row_idx + 1,
+                                    parent_id_clean
+                                );
+                            }
+                        }
+                    }
+                }
+
+                if entry.aggregated.len() < aggregate_idxs.len() {
+                    entry.aggregated.resize(aggregate_idxs.len(), Vec::new());
+                }
+                for ((aggregate, idx), values) in options
+                    .aggregates
+                    .iter()
+                    .zip(&aggregate_idxs)
+                    .zip(entry.aggregated.iter_mut())
+                {
+                    let Some(raw) = idx.and_then(|idx| record.get(idx)) else {
+                        continue;
+                    };
+                    for value in raw.split('|').map(normalize_cell) {
+                        if value.is_empty() || (aggregate.distinct && values.iter().any(|v| v == value)) {
+                            continue;
+                        }
+                        values.push(value.to_string());
+                    }
+                }
+
                 // Prefer explicit field_date; fall back to parsing from the file title
                 let mut date_source: Option<&str> = None;
                 if let Some(idx) = field_date_idx_opt {
@@ -179,7 +721,7 @@ impl ItemCsvGenerator {
                 }
 
                 if let Some(src) = date_source {
-                    if let Some((year, maybe_month)) = parse_year_and_month(src) {
+                    if let Some((year, maybe_month)) = parse_year_and_month(src, options.season_convention) {
                         entry.total_date_samples += 1;
                         *entry.year_counts.entry(year).or_insert(0) += 1;
                         if let Some(m) = maybe_month {
@@ -190,12 +732,39 @@ impl ItemCsvGenerator {
             }
         }
 
-        stats.unique_parents = parent_data.len();
+        if stats.total_items > 0 && stats.skipped_rows == stats.total_items {
+            anyhow::bail!(
+                "Column '{}' is empty in all {} rows, so there are no parents to generate items for. \
+                 Was the input processed with the parent_id modifier (e.g. not excluded via --ignore-run parent-id)?",
+                parent_id_column,
+                stats.total_items
+            );
+        }
 
-        let output_file = File::create(output_path).context("Failed to create output file")?;
-        let mut writer = Writer::from_writer(output_file);
+        // Container rows title their parent but are not items themselves. A parent with only a
+        // container row has no items, so it gets no items row.
+        let mut container_titles = HashMap::with_capacity(containers.len());
+        let mut container_only: Vec<&String> = Vec::new();
+        for (parent_id, (title, rows)) in &containers {
+            stats.total_items -= rows;
+            if parent_data.contains_key(parent_id) {
+                stats.containers_promoted += rows;
+                if let Some(title) = title {
+                    container_titles.insert(parent_id.as_str(), title.as_str());
+                }
+            } else {
+                container_only.push(parent_id);
+            }
+        }
+        container_only.sort();
+        for parent_id in container_only {
+            warn!(
+                "Parent '{}' has a container row but no item rows; no items row is written for it.",
+                parent_id
+            );
+        }
 
-        writer.write_record([
+        let mut output_headers = vec![
             "file_identifier",
             "title",
             "# of items",
@@ -203,15 +772,24 @@ impl ItemCsvGenerator {
             "field_edtf_date",
             "field_identifier",
             "field_archival_level",
-        ])?;
+        ];
+        output_headers.extend(options.aggregates.iter().map(|a| a.column.as_str()));
+
+        stats.unique_parents = parent_data.len();
 
-        let mut sorted_data: Vec<_> = parent_data.into_iter().collect();
-        sorted_data.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut items_rows = if options.append {
+            read_existing_items(output_path, &output_headers)?
+        } else {
+            BTreeMap::new()
+        };
 
         let node_value = node.unwrap_or("");
 
-        for (file_identifier, group) in sorted_data {
-            let count_str = group.count.to_string();
+        for (file_identifier, group) in parent_data {
+            let count_str = match group.external_count {
+                Some(n) => n.to_string(),
+                None => group.count.to_string(),
+            };
 
             // Decide field_date for the group:
             let field_date_value = if group.total_date_samples == 0 {
@@ -226,47 +804,60 @@ impl ItemCsvGenerator {
 
                 if let Some((y, m, c)) = dominant_ym {
                     if c * 2 > group.total_date_samples {
-                        // Format MM/YYYY
-                        format!("{:02}/{}", m, y)
+                        // Format MM/YYYY (or YYYY-SS for EDTF season codes)
+                        format_year_month(y, m)
                     } else {
                         // Fallback to average year
-                        let (sum, total): (u32, u32) = group
-                            .year_counts
-                            .iter()
-                            .fold((0u32, 0u32), |(s, t), (&yy, &cnt)| (s + (yy as u32) * (cnt as u32), t + cnt as u32));
-                        let avg = if total > 0 {
-                            ((sum as f64) / (total as f64)).round() as u16
-                        } else {
-                            y
-                        };
-                        avg.to_string()
+                        average_year(&group.year_counts, options.date_average_rounding)
+                            .unwrap_or(y)
+                            .to_string()
                     }
                 } else {
                     // No month info found; average the year
-                    let (sum, total): (u32, u32) = group
-                        .year_counts
-                        .iter()
-                        .fold((0u32, 0u32), |(s, t), (&yy, &cnt)| (s + (yy as u32) * (cnt as u32), t + cnt as u32));
-                    if total > 0 {
-                        (((sum as f64) / (total as f64)).round() as u16).to_string()
-                    } else {
-                        String::new()
-                    }
+                    average_year(&group.year_counts, options.date_average_rounding)
+                        .map(|year| year.to_string())
+                        .unwrap_or_default()
                 }
             };
 
-            writer.write_record([
-                file_identifier.as_str(),
-                group.title.as_str(),
-                count_str.as_str(),
-                node_value,
-                field_date_value.as_str(),
-                file_identifier.as_str(),
-                "File",
-            ])?;
+            let aggregated_values: Vec<String> = (0..options.aggregates.len())
+                .map(|i| group.aggregated.get(i).map(|v| v.join("|")).unwrap_or_default())
+                .collect();
+
+            let title = match container_titles.get(file_identifier.as_str()) {
+                Some(title) => title.to_string(),
+                None => group.title,
+            };
+            let mut row = vec![
+                file_identifier.clone(),
+                title,
+                count_str,
+                node_value.to_string(),
+                field_date_value,
+                file_identifier.clone(),
+                "File".to_string(),
+            ];
+            row.extend(aggregated_values);
+            match items_rows.get_mut(&file_identifier) {
+                Some(existing) => merge_items_row(existing, row, &options.aggregates, output_path)?,
+                None => {
+                    items_rows.insert(file_identifier, row);
+                }
+            }
         }
 
+        let output_file = File::create(output_path).context("Failed to create output file")?;
+        let mut writer = Writer::from_writer(output_file);
+        writer.write_record(&output_headers)?;
+        for row in items_rows.values() {
+            writer.write_record(row)?;
+        }
         writer.flush()?;
+
+        // Only once the counts are on disk, so a failed run leaves its rows uncounted.
+        if let Some(ledger) = ledger {
+            ledger.save()?;
+        }
         Ok(stats)
     }
 }
@@ -1,26 +1,42 @@
 pub mod cli;
 pub mod csv_modifier;
+mod dedup;
 pub mod google_sheets;
 pub mod item_csv_generator;
 pub mod pipeline;
 pub mod modifiers;
+pub mod watch;
 
 pub use cli::{Cli, Commands, Modifier};
-pub use csv_modifier::{ColumnModifier, CsvModifier, ProcessingStats, RowContext};
-pub use item_csv_generator::{ItemCsvGenerator, ItemGenerationStats};
+pub use csv_modifier::{
+    ColumnModifier, CsvDialect, CsvModifier, OutputFormat, ProcessingStats, RowContext,
+    FILE_ORIGINAL_COLUMN, TRANSFORM_LOG_TARGET,
+};
+pub use item_csv_generator::{
+    DateAverageRounding, ItemAggregate, ItemBatchReport, ItemCsvGenerator, ItemErrorPolicy,
+    ItemGenerationOptions, ItemGenerationStats, SeasonConvention,
+};
 pub use modifiers::{
-    AccessIdentifierValidator, CopyFromColumnModifier, FieldModelModifier, FileExtensionModifier,
-    LanguageModifier, ParentIdModifier, DEFAULT_LANGUAGE_CODE_PATH, resolve_language_mapping_url,
+    AccessIdentifierValidator, CopyFromColumnModifier, ExecModifier, FieldModelModifier,
+    FileExtensionModifier, LanguageModifier, ParentIdModifier, TitleTidyModifier, UrlValidator,
+    DEFAULT_EXEC_TIMEOUT, DEFAULT_LANGUAGE_CODE_PATH, DEFAULT_PARENT_ID_COLUMN, EXEC_ROW_ENV,
+    is_container_identifier, parse_exec_modifier, parse_model_rename,
+    resolve_language_mapping_url,
 };
 
 pub use pipeline::{
     ProcessResult,
+    ProcessingOptions,
+    check_output_collisions,
     determine_items_output_path,
     determine_processed_output_path,
     determine_processed_output_path_for_sheets,
     generate_items_from_path,
+    generate_items_from_paths,
     generate_items_from_source,
     generate_items_from_url,
     process_csv_and_maybe_generate_items,
     process_google_sheets_and_maybe_generate_items,
+    process_google_sheets_batch_and_maybe_generate_items,
+    read_url_file,
 };
@@ -1,55 +1,192 @@
 use anyhow::Result;
 use clap::Parser;
 use env_logger::Env;
+use log::LevelFilter;
+use std::time::Duration;
 use organise::{
-    Cli, Commands, CsvModifier, ItemGenerationStats, ProcessingStats,
+    Cli, Commands, CsvDialect, CsvModifier, ItemGenerationOptions, ItemGenerationStats, ProcessingOptions,
+    ProcessingStats,
+    determine_items_output_path,
+    generate_items_from_paths,
     generate_items_from_source,
     process_csv_and_maybe_generate_items,
     process_google_sheets_and_maybe_generate_items,
+    process_google_sheets_batch_and_maybe_generate_items,
+    read_url_file,
+    watch::{watch_file, DEFAULT_DEBOUNCE},
+    TRANSFORM_LOG_TARGET,
 };
 
 fn main() -> Result<()> {
-    init_logging();
     let cli = Cli::parse();
+    init_logging(cli.transform_log_level);
+    let processing_options = ProcessingOptions {
+        validate_urls: cli.validate_urls.clone(),
+        tidy_titles: cli.tidy_titles.clone(),
+        exec_modifiers: cli.exec_modifier.clone(),
+        allow_exec: cli.allow_exec,
+        exec_timeout: Some(Duration::from_secs(cli.exec_timeout)),
+        fix_missing_scheme: cli.fix_missing_scheme,
+        dedup_kept_output: cli.dedup_kept_output.clone(),
+        dedup_memory_cap: cli.dedup_memory_cap,
+        crosswalk: cli.crosswalk.clone(),
+        gid: cli.gid.clone(),
+        all_tabs: cli.all_tabs,
+        fetch_concurrency: cli.fetch_concurrency,
+        excel_friendly: cli.excel_friendly,
+        canonical: cli.canonical,
+        verify_passthrough: cli.verify_passthrough,
+        parent_id_column: cli.parent_id_column.clone(),
+        model_renames: cli.rename_model.clone(),
+        output_format: cli.output_format,
+        keep_original_file_column: cli.keep_original_file_column,
+        protect_columns: cli.protect_columns.clone(),
+        keep_containers: cli.keep_containers,
+        strict: cli.strict,
+        input_dialect: CsvDialect {
+            quote: cli.quote_char.unwrap_or(b'"'),
+            escape: cli.escape_char,
+        },
+        keep_dialect: cli.keep_dialect,
+        repair_unquoted_commas: cli.repair_unquoted_commas,
+    };
+    let items_options = ItemGenerationOptions {
+        count_column: cli.count_column.clone(),
+        parent_id_column: cli.parent_id_column.clone(),
+        season_convention: cli.season_dates,
+        date_average_rounding: cli.date_average_rounding,
+        aggregates: cli.items_aggregate.clone(),
+        promote_containers: cli.promote_containers,
+        append: cli.append,
+        ledger: cli.items_ledger.clone(),
+    };
+
+    if cli.watch && cli.append && cli.items_ledger.is_none() {
+        anyhow::bail!(
+            "--watch with --append would add the whole batch again on every save; pass --items-ledger so rows are only counted once"
+        );
+    }
 
     match cli.command {
         Some(Commands::GenerateItems {
             input,
             url,
             output,
+            output_dir,
+            on_error,
             node,
+            count_column,
+            parent_id_column,
+            season_dates,
+            date_average_rounding,
+            items_aggregate,
+            promote_containers,
+            append,
+            items_ledger,
         }) => {
+            let items_options = ItemGenerationOptions {
+                count_column,
+                parent_id_column,
+                season_convention: season_dates,
+                date_average_rounding,
+                aggregates: items_aggregate,
+                promote_containers,
+                append,
+                ledger: items_ledger,
+            };
+            if input.len() > 1 {
+                if output.is_some() {
+                    anyhow::bail!("--output takes a single input; use --output-dir with several inputs");
+                }
+                if items_options.ledger.is_some() {
+                    anyhow::bail!("--items-ledger tracks a single items file; use it with a single input");
+                }
+                let report = generate_items_from_paths(
+                    &input,
+                    output_dir.as_deref(),
+                    node.as_deref(),
+                    &items_options,
+                    on_error,
+                )?;
+                for (_, items_path, stats) in &report.generated {
+                    print_item_generation_summary(stats, items_path);
+                }
+                if !report.failed.is_empty() {
+                    // Each failure was already logged as it was skipped.
+                    anyhow::bail!(
+                        "Items generation failed for {} of {} inputs",
+                        report.failed.len(),
+                        input.len()
+                    );
+                }
+                return Ok(());
+            }
+
+            // A relative --output (or the default items.csv) goes under --output-dir.
+            let output = determine_items_output_path(
+                input.first().map_or("", String::as_str),
+                Some(output.as_deref().unwrap_or("items.csv")),
+                output_dir.as_deref(),
+            )?;
             let stats = generate_items_from_source(
-                input.as_deref(),
+                input.first().map(String::as_str),
                 url.as_deref(),
-                output.as_deref(),
+                Some(&output),
                 node.as_deref(),
+                &items_options,
             )?;
-            print_item_generation_summary(&stats, output.as_deref().unwrap_or("items.csv"));
+            print_item_generation_summary(&stats, &output);
         }
         None => match (cli.input.as_deref(), cli.url.as_deref()) {
             (Some(input_path), None) => {
-                println!("Processing file: {}", input_path);
-                let res = process_csv_and_maybe_generate_items(
-                    input_path,
-                    cli.output.as_deref(),
-                    cli.output_dir.as_deref(),
-                    &cli.only_run,
-                    &cli.ignore_run,
-                    cli.language_url.as_deref(),
-                    cli.full,
-                    cli.items_output.as_deref(),
-                    cli.node.as_deref(),
-                )?;
-                print_processing_summary(&res.processing_stats, &res.processed_output_path, cli.stats);
+                let process = || -> Result<()> {
+                    println!("Processing file: {}", input_path);
+                    let res = process_csv_and_maybe_generate_items(
+                        input_path,
+                        cli.output.as_deref(),
+                        cli.output_dir.as_deref(),
+                        &cli.only_run,
+                        &cli.ignore_run,
+                        cli.language_url.as_deref(),
+                        &processing_options,
+                        cli.full,
+                        cli.items_output.as_deref(),
+                        cli.node.as_deref(),
+                        &items_options,
+                    )?;
+                    print_processing_summary(&res.processing_stats, &res.processed_output_path, cli.stats);
 
-                if let (Some(items_stats), Some(items_path)) = (res.items_stats.as_ref(), res.items_output_path.as_ref()) {
-                    print_item_generation_summary(items_stats, items_path);
+                    if let (Some(items_stats), Some(items_path)) = (res.items_stats.as_ref(), res.items_output_path.as_ref()) {
+                        print_item_generation_summary(items_stats, items_path);
+                    }
+                    Ok(())
+                };
+
+                if cli.watch {
+                    // A failed run (e.g. mid-edit CSV) is reported and the watch continues.
+                    let run_and_report = || {
+                        if let Err(err) = process() {
+                            eprintln!("Error: {:#}", err);
+                        }
+                        println!("\nWatching {} for changes (Ctrl-C to exit)...", input_path);
+                    };
+                    run_and_report();
+                    watch_file(input_path, DEFAULT_DEBOUNCE, run_and_report)?;
+                } else {
+                    process()?;
                 }
             }
             (None, Some(url)) => {
+                let csv_url = match cli.gid.as_deref() {
+                    Some(gid) => CsvModifier::google_sheets_to_csv_url_for_gid(url, gid)?,
+                    None => CsvModifier::google_sheets_to_csv_url(url)?,
+                };
+                if cli.print_url_only {
+                    println!("{}", csv_url);
+                    return Ok(());
+                }
+
                 println!("Processing Google Sheets URL: {}", url);
-                let csv_url = CsvModifier::google_sheets_to_csv_url(url)?;
                 println!("CSV export URL: {}", csv_url);
 
                 let res = process_google_sheets_and_maybe_generate_items(
@@ -59,9 +196,11 @@ fn main() -> Result<()> {
                     &cli.only_run,
                     &cli.ignore_run,
                     cli.language_url.as_deref(),
+                    &processing_options,
                     cli.full,
                     cli.items_output.as_deref(),
                     cli.node.as_deref(),
+                    &items_options,
                 )?;
                 print_processing_summary(&res.processing_stats, &res.processed_output_path, cli.stats);
 
@@ -73,9 +212,33 @@ fn main() -> Result<()> {
                 anyhow::bail!("Specify either a file path or --url, not both");
             }
             (None, None) => {
-                anyhow::bail!(
-                    "No input provided. Pass a file path or use --url with a Google Sheets link"
-                );
+                let Some(url_file) = cli.url_file.as_deref() else {
+                    anyhow::bail!(
+                        "No input provided. Pass a file path or use --url with a Google Sheets link"
+                    );
+                };
+                let urls = read_url_file(url_file)?;
+                println!("Processing {} Google Sheets URLs from {}", urls.len(), url_file);
+
+                let results = process_google_sheets_batch_and_maybe_generate_items(
+                    &urls,
+                    cli.output_dir.as_deref(),
+                    &cli.only_run,
+                    &cli.ignore_run,
+                    cli.language_url.as_deref(),
+                    &processing_options,
+                    cli.full,
+                    cli.node.as_deref(),
+                    &items_options,
+                )?;
+                for (url, res) in urls.iter().zip(&results) {
+                    println!("\nGoogle Sheets URL: {}", url);
+                    print_processing_summary(&res.processing_stats, &res.processed_output_path, cli.stats);
+
+                    if let (Some(items_stats), Some(items_path)) = (res.items_stats.as_ref(), res.items_output_path.as_ref()) {
+                        print_item_generation_summary(items_stats, items_path);
+                    }
+                }
             }
         },
     }
@@ -83,9 +246,13 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn init_logging() {
+fn init_logging(transform_level: Option<LevelFilter>) {
     let env = Env::default().filter_or("RUST_LOG", "warn");
-    let _ = env_logger::Builder::from_env(env)
+    let mut builder = env_logger::Builder::from_env(env);
+    if let Some(level) = transform_level {
+        builder.filter_module(TRANSFORM_LOG_TARGET, level);
+    }
+    let _ = builder
         .format_timestamp_secs()
         .format_target(false)
         .try_init();
@@ -99,6 +266,19 @@ fn print_processing_summary(stats: &ProcessingStats, output: &str, show_stats: b
     if stats.validation_failures > 0 {
         println!("WARNING: {} validation failures", stats.validation_failures);
     }
+    if stats.extension_mismatches > 0 {
+        println!(
+            "WARNING: {} rows where the file suffix differs from file_extension",
+            stats.extension_mismatches
+        );
+    }
+
+    if stats.unquoted_comma_repairs > 0 {
+        println!(
+            "WARNING: {} rows repaired by rejoining an unquoted comma into the title; review them",
+            stats.unquoted_comma_repairs
+        );
+    }
 
     println!("Output written to: {}", output);
 
@@ -119,6 +299,21 @@ fn print_item_generation_summary(stats: &ItemGenerationStats, output: &str) {
             stats.skipped_rows
         );
     }
+    if stats.containers_promoted > 0 {
+        println!("  - Container rows used as parent titles: {}", stats.containers_promoted);
+    }
+    if stats.ledger_untracked_rows > 0 {
+        println!(
+            "  \u{26a0} Not counted: {} rows without an accessIdentifier for the items ledger",
+            stats.ledger_untracked_rows
+        );
+    }
+    if stats.ledger_skipped_rows > 0 {
+        println!(
+            "  - Rows already counted per the items ledger: {}",
+            stats.ledger_skipped_rows
+        );
+    }
 }
 
 fn print_detailed_stats(stats: &ProcessingStats) {
@@ -126,6 +321,9 @@ fn print_detailed_stats(stats: &ProcessingStats) {
     println!("- Total rows processed: {}", stats.total_rows);
     println!("- Rows skipped: {}", stats.skipped_rows);
     println!("- Cells modified: {}", stats.cells_modified);
+    println!("  - Sanitized (NBSP / mojibake): {}", stats.cells_sanitized);
+    println!("  - Changed by modifiers: {}", stats.cells_modified_by_modifiers);
+    println!("  - Subdelimiter (; -> |): {}", stats.cells_subdelimiter_replaced);
     println!("- Validation failures: {}", stats.validation_failures);
     println!("- Columns processed: {}", stats.columns_processed.len());
 
@@ -1,6 +1,23 @@
 use crate::csv_modifier::{normalize_cell, ColumnModifier, RowContext};
 
-pub struct AccessIdentifierValidator;
+/// Whether an accessIdentifier names a container (box / folder) rather than an item, i.e. ends in
+/// `_00` or `_000`.
+pub fn is_container_identifier(value: &str) -> bool {
+    let clean = normalize_cell(value);
+    clean.ends_with("_00") || clean.ends_with("_000")
+}
+
+/// Rejects empty accessIdentifiers and, unless `keep_containers` is set, container rows.
+#[derive(Default)]
+pub struct AccessIdentifierValidator {
+    pub keep_containers: bool,
+}
+
+impl AccessIdentifierValidator {
+    pub const fn new(keep_containers: bool) -> Self {
+        Self { keep_containers }
+    }
+}
 
 impl ColumnModifier for AccessIdentifierValidator {
     fn modify(&self, value: &str, _row: &RowContext) -> String {
@@ -18,6 +35,6 @@ impl ColumnModifier for AccessIdentifierValidator {
             return false;
         }
 
-        !(clean.ends_with("_00") || clean.ends_with("_000"))
+        self.keep_containers || !is_container_identifier(clean)
     }
 }
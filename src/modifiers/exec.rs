@@ -0,0 +1,224 @@
+use crate::csv_modifier::{ColumnModifier, RowContext};
+use anyhow::{Context, Result};
+use std::cell::RefCell;
+use std::io::{Read, Write};
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Environment variable holding the whole row as a JSON object (header → value).
+pub const EXEC_ROW_ENV: &str = "ORGANISE_ROW";
+
+/// Default limit for a single command invocation.
+pub const DEFAULT_EXEC_TIMEOUT: Duration = Duration::from_secs(10);
+
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Stderr beyond this many characters is cut from validation logs.
+const MAX_STDERR_IN_REASON: usize = 200;
+
+/// Parses `--exec-modifier` values of the form `COLUMN=COMMAND`.
+pub fn parse_exec_modifier(value: &str) -> Result<(String, String)> {
+    let (column, command) = value
+        .split_once('=')
+        .with_context(|| format!("Invalid exec modifier '{}': expected COLUMN=COMMAND", value))?;
+    let (column, command) = (column.trim(), command.trim());
+    if column.is_empty() || command.is_empty() {
+        anyhow::bail!(
+            "Invalid exec modifier '{}': COLUMN and COMMAND must be non-empty",
+            value
+        );
+    }
+    Ok((column.to_string(), command.to_string()))
+}
+
+/// Replaces each cell with the stdout of an external shell command.
+///
+/// The command runs once per row through `sh -c` (`cmd /C` on Windows) with the cell value on
+/// stdin and the row as JSON in [`EXEC_ROW_ENV`]. One trailing newline is removed from stdout.
+/// A non-zero exit, a timeout or a failure to start is reported as a validation failure and the
+/// cell is left unchanged. On Unix a timeout kills the command together with anything it started.
+/// The command is not run on protected columns.
+///
+/// The command runs with the user's full permissions; only register commands you trust.
+pub struct ExecModifier {
+    command: String,
+    timeout: Duration,
+    /// Outcome of the run made by `validate`, reused by `validation_reason` / `modify`.
+    last_run: RefCell<Option<(String, Result<String, String>)>>,
+}
+
+impl ExecModifier {
+    pub fn new(command: &str, timeout: Duration) -> Self {
+        Self {
+            command: command.to_string(),
+            timeout,
+            last_run: RefCell::new(None),
+        }
+    }
+
+    fn shell(&self) -> Command {
+        if cfg!(windows) {
+            let mut command = Command::new("cmd");
+            command.arg("/C").arg(&self.command);
+            command
+        } else {
+            let mut command = Command::new("sh");
+            command.arg("-c").arg(&self.command);
+            // Its own process group, so a timeout can kill whatever the shell started as well.
+            #[cfg(unix)]
+            std::os::unix::process::CommandExt::process_group(&mut command, 0);
+            command
+        }
+    }
+
+    /// Runs the command for one cell; `Err` carries a human-readable failure reason.
+    fn run(&self, value: &str, row: &RowContext) -> Result<String, String> {
+        let mut child = self
+            .shell()
+            .env(EXEC_ROW_ENV, row.to_json())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| format!("command '{}' could not start: {}", self.command, err))?;
+
+        // Feed stdin and drain the pipes on their own threads so a chatty child cannot block.
+        let stdin = child.stdin.take().map(|mut stdin| {
+            let input = value.to_string();
+            thread::spawn(move || {
+                let _ = stdin.write_all(input.as_bytes());
+            })
+        });
+        let stdout = child.stdout.take().map(read_to_end_in_background);
+        let stderr = child.stderr.take().map(read_to_end_in_background);
+
+        let status = wait_with_timeout(&mut child, self.timeout)
+            .map_err(|err| format!("command '{}' failed: {}", self.command, err))?;
+        let collect = |handle: Option<thread::JoinHandle<Vec<u8>>>| {
+            handle
+                .and_then(|h| h.join().ok())
+                .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+                .unwrap_or_default()
+        };
+        let Some(status) = status else {
+            // On Unix the whole process group is dead, so the pipes are closed and the threads end.
+            // Elsewhere a grandchild of `cmd` may still hold them, so the threads are left to finish.
+            if cfg!(unix) {
+                if let Some(handle) = stdin {
+                    let _ = handle.join();
+                }
+                collect(stdout);
+                collect(stderr);
+            }
+            return Err(format!(
+                "command '{}' timed out after {:?}",
+                self.command, self.timeout
+            ));
+        };
+        if let Some(handle) = stdin {
+            let _ = handle.join();
+        }
+        let stdout = collect(stdout);
+        let stderr = collect(stderr);
+
+        if !status.success() {
+            let stderr = stderr.trim();
+            let mut reason = format!("command '{}' exited with {}", self.command, status);
+            if !stderr.is_empty() {
+                let shown: String = stderr.chars().take(MAX_STDERR_IN_REASON).collect();
+                reason.push_str(&format!(": {}", shown));
+            }
+            return Err(reason);
+        }
+
+        let output = stdout
+            .strip_suffix('\n')
+            .map(|s| s.strip_suffix('\r').unwrap_or(s))
+            .unwrap_or(&stdout);
+        Ok(output.to_string())
+    }
+
+    /// Result for this cell, reusing the run made by `validate` when it was for the same value.
+    fn outcome(&self, value: &str, row: &RowContext) -> Result<String, String> {
+        if let Some((cached_value, outcome)) = self.last_run.borrow().as_ref() {
+            if cached_value == value {
+                return outcome.clone();
+            }
+        }
+        let outcome = self.run(value, row);
+        *self.last_run.borrow_mut() = Some((value.to_string(), outcome.clone()));
+        outcome
+    }
+}
+
+fn read_to_end_in_background<R: Read + Send + 'static>(mut pipe: R) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        let _ = pipe.read_to_end(&mut buffer);
+        buffer
+    })
+}
+
+/// Waits for `child` to exit; kills it (and on Unix its process group) and returns `None` once
+/// `timeout` has passed.
+fn wait_with_timeout(
+    child: &mut Child,
+    timeout: Duration,
+) -> std::io::Result<Option<std::process::ExitStatus>> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if Instant::now() >= deadline {
+            kill_process_group(child);
+            let _ = child.kill();
+            let _ = child.wait();
+            return Ok(None);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Kills the process group led by `child` (see [`ExecModifier::shell`]).
+#[cfg(unix)]
+fn kill_process_group(child: &Child) {
+    if let Ok(pgid) = libc::pid_t::try_from(child.id()) {
+        // SAFETY: kill(2) takes no pointers; a negative pid signals the group `pgid` leads.
+        unsafe {
+            libc::kill(-pgid, libc::SIGKILL);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn kill_process_group(_child: &Child) {}
+
+impl ColumnModifier for ExecModifier {
+    fn modify(&self, value: &str, row: &RowContext) -> String {
+        let outcome = self.outcome(value, row);
+        *self.last_run.borrow_mut() = None;
+        outcome.unwrap_or_else(|_| value.to_string())
+    }
+
+    fn description(&self) -> &str {
+        "Replaces the cell with the output of an external command"
+    }
+
+    fn validate(&self, value: &str, row: &RowContext) -> bool {
+        *self.last_run.borrow_mut() = None;
+        self.outcome(value, row).is_ok()
+    }
+
+    fn validation_reason(&self, value: &str, row: &RowContext) -> Option<String> {
+        let reason = self.outcome(value, row).err();
+        *self.last_run.borrow_mut() = None;
+        reason
+    }
+
+    /// The command's output could not be written to a protected column, so it is not run.
+    fn runs_on_protected_columns(&self) -> bool {
+        false
+    }
+}
@@ -35,6 +35,19 @@ struct FieldModelConfig {
 pub struct FieldModelModifier {
     mappings: HashMap<String, String>,
     default_model: String,
+    renames: HashMap<String, String>,
+}
+
+/// Parses a `from=to` model rename (`--rename-model`), e.g. `Image=Digital Image`.
+pub fn parse_model_rename(value: &str) -> Result<(String, String)> {
+    let (from, to) = value
+        .split_once('=')
+        .with_context(|| format!("Invalid model rename '{}': expected FROM=TO", value))?;
+    let (from, to) = (from.trim(), to.trim());
+    if from.is_empty() || to.is_empty() {
+        anyhow::bail!("Invalid model rename '{}': FROM and TO must be non-empty", value);
+    }
+    Ok((from.to_string(), to.to_string()))
 }
 
 impl FieldModelModifier {
@@ -79,9 +92,20 @@ impl FieldModelModifier {
         Ok(Self {
             mappings,
             default_model,
+            renames: HashMap::new(),
         })
     }
 
+    /// Relabels derived models just before they are written, e.g. `Image` → `Digital Image`,
+    /// so the mapping config can stay portable across Workbench instances.
+    pub fn with_model_renames<I>(mut self, renames: I) -> Self
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        self.renames.extend(renames);
+        self
+    }
+
     fn model_for_extension(&self, extension: &str) -> &str {
         let key = normalize_extension(extension);
         if key.is_empty() {
@@ -101,6 +125,11 @@ impl ColumnModifier for FieldModelModifier {
             .get_first_non_empty(&["file_extension", "file_extention"])
             .unwrap_or("");
         let target_model = self.model_for_extension(extension);
+        let target_model = self
+            .renames
+            .get(target_model)
+            .map(|s| s.as_str())
+            .unwrap_or(target_model);
         let current_value = normalize_cell(value);
 
         if current_value == target_model {
@@ -165,4 +194,30 @@ mod tests {
         let updated = modifier.modify("", &context);
         assert_eq!(updated, "Binary");
     }
+
+    #[test]
+    fn renames_derived_model() {
+        let modifier = FieldModelModifier::from_default_config()
+            .unwrap()
+            .with_model_renames([parse_model_rename("Image=Digital Image").unwrap()]);
+        let headers = vec!["file_extension".to_string(), "field_model".to_string()];
+
+        let values = vec!["jpg".to_string(), "Image".to_string()];
+        let context = RowContext::new(&headers, &values, 0);
+        assert_eq!(modifier.modify("Image", &context), "Digital Image");
+
+        let values = vec!["mp3".to_string(), String::new()];
+        let context = RowContext::new(&headers, &values, 0);
+        assert_eq!(modifier.modify("", &context), "Audio");
+    }
+
+    #[test]
+    fn rejects_malformed_model_rename() {
+        assert!(parse_model_rename("Image").is_err());
+        assert!(parse_model_rename("=Digital Image").is_err());
+        assert_eq!(
+            parse_model_rename(" Image = Digital Image ").unwrap(),
+            ("Image".to_string(), "Digital Image".to_string())
+        );
+    }
 }
@@ -1,4 +1,5 @@
 use crate::csv_modifier::{normalize_cell, ColumnModifier, RowContext};
+use crate::modifiers::parent_id::derive_parent_id;
 
 pub struct FileExtensionModifier;
 
@@ -14,11 +15,7 @@ impl ColumnModifier for FileExtensionModifier {
             return value_clean.to_string();
         }
 
-        let parent_id = if let Some(last_underscore) = access_identifier.rfind('_') {
-            &access_identifier[..last_underscore]
-        } else {
-            access_identifier
-        };
+        let parent_id = derive_parent_id(access_identifier);
 
         let base_name = if let Some(dot_pos) = value_clean.rfind('.') {
             &value_clean[..dot_pos]
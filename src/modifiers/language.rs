@@ -1,4 +1,4 @@
-use crate::csv_modifier::{ColumnModifier, RowContext};
+use crate::csv_modifier::{ColumnModifier, RowContext, TRANSFORM_LOG_TARGET};
 use anyhow::{Context, Result};
 use log::{debug, info, warn};
 use serde::Deserialize;
@@ -82,10 +82,12 @@ impl LanguageModifier {
             );
         } else {
             info!(
+                target: TRANSFORM_LOG_TARGET,
                 "Language modifier: loaded {} code→term mappings from {}",
                 n, api_url
             );
             debug!(
+                target: TRANSFORM_LOG_TARGET,
                 "Language modifier: sample codes in map: {:?}",
                 code_to_tid.keys().take(8).collect::<Vec<_>>()
             );
@@ -117,6 +119,7 @@ impl Drop for LanguageModifier {
             );
         } else {
             info!(
+                target: TRANSFORM_LOG_TARGET,
                 "Language modifier: {} cells mapped to term ID, {} empty cells skipped ({} `field_language` cells total).",
                 m, e, total
             );
@@ -135,6 +138,7 @@ impl ColumnModifier for LanguageModifier {
         if let Some(tid) = self.code_to_tid.get(&normalized) {
             self.matched.fetch_add(1, Ordering::Relaxed);
             debug!(
+                target: TRANSFORM_LOG_TARGET,
                 "Language modifier: {:?} → tid {}",
                 value.trim(),
                 tid
@@ -143,6 +147,7 @@ impl ColumnModifier for LanguageModifier {
         } else {
             self.unmatched.fetch_add(1, Ordering::Relaxed);
             debug!(
+                target: TRANSFORM_LOG_TARGET,
                 "Language modifier: no map entry for code {:?} (left unchanged)",
                 value.trim()
             );
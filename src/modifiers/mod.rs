@@ -1,13 +1,21 @@
 pub mod access_identifier;
 pub mod copy_column;
+pub mod exec;
 pub mod field_model;
 pub mod file_extension;
 pub mod language;
 pub mod parent_id;
+pub mod title_tidy;
+pub mod url_validator;
 
-pub use access_identifier::AccessIdentifierValidator;
+pub use access_identifier::{is_container_identifier, AccessIdentifierValidator};
 pub use copy_column::CopyFromColumnModifier;
-pub use field_model::FieldModelModifier;
+pub use exec::{parse_exec_modifier, ExecModifier, DEFAULT_EXEC_TIMEOUT, EXEC_ROW_ENV};
+pub use field_model::{parse_model_rename, FieldModelModifier};
 pub use file_extension::FileExtensionModifier;
 pub use language::{LanguageModifier, resolve_language_mapping_url, DEFAULT_LANGUAGE_CODE_PATH};
-pub use parent_id::ParentIdModifier;
+pub use parent_id::{
+    derive_parent_id, ParentIdModifier, DEFAULT_PARENT_ID_COLUMN, PARENT_ID_SEPARATOR,
+};
+pub use title_tidy::TitleTidyModifier;
+pub use url_validator::UrlValidator;
@@ -1,20 +1,27 @@
 use crate::csv_modifier::{ColumnModifier, RowContext};
 
+/// Column `ParentIdModifier` writes to and `ItemCsvGenerator` groups by unless overridden.
+pub const DEFAULT_PARENT_ID_COLUMN: &str = "parent_id";
+
+/// Separator between the parent segment(s) and the item segment of an `accessIdentifier`.
+pub const PARENT_ID_SEPARATOR: char = '_';
+
+/// Derives the parent identifier by dropping the last separator-delimited segment.
+///
+/// Shared by [`ParentIdModifier`] and `FileExtensionModifier` so the `parent_id` column and the
+/// directory part of `file` can never be derived differently.
+pub fn derive_parent_id(access_identifier: &str) -> &str {
+    match access_identifier.rfind(PARENT_ID_SEPARATOR) {
+        Some(last_separator) => &access_identifier[..last_separator],
+        None => access_identifier,
+    }
+}
+
 pub struct ParentIdModifier;
 
 impl ColumnModifier for ParentIdModifier {
     fn modify(&self, _value: &str, row: &RowContext) -> String {
-        let access_identifier = row.get_or_empty("accessIdentifier");
-
-        if !access_identifier.is_empty() {
-            if let Some(last_underscore) = access_identifier.rfind('_') {
-                access_identifier[..last_underscore].to_string()
-            } else {
-                access_identifier.to_string()
-            }
-        } else {
-            String::new()
-        }
+        derive_parent_id(row.get_or_empty("accessIdentifier")).to_string()
     }
 
     fn description(&self) -> &str {
@@ -25,3 +32,41 @@ impl ColumnModifier for ParentIdModifier {
         !row.get_or_empty("accessIdentifier").is_empty()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modifiers::FileExtensionModifier;
+
+    #[test]
+    fn derive_parent_id_drops_last_segment() {
+        assert_eq!(derive_parent_id("2024_19_01_001"), "2024_19_01");
+        assert_eq!(derive_parent_id("standalone"), "standalone");
+        assert_eq!(derive_parent_id(""), "");
+    }
+
+    #[test]
+    fn file_directory_matches_parent_id() {
+        let headers = vec![
+            "accessIdentifier".to_string(),
+            "file".to_string(),
+            "file_extension".to_string(),
+            "parent_id".to_string(),
+        ];
+
+        for access_identifier in ["2024_19_01_001", "box-7_env_3_012", "noseparator"] {
+            let values = vec![
+                access_identifier.to_string(),
+                "scan".to_string(),
+                "tif".to_string(),
+                String::new(),
+            ];
+            let context = RowContext::new(&headers, &values, 0);
+
+            let parent_id = ParentIdModifier.modify("", &context);
+            let file = FileExtensionModifier.modify("scan", &context);
+
+            assert_eq!(file, format!("{}/scan.tif", parent_id));
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use crate::csv_modifier::{ColumnModifier, RowContext};
+
+/// Words that end in a period as an abbreviation, compared case-insensitively without the period.
+const ABBREVIATIONS: &[&str] = &[
+    "approx", "bros", "ca", "ch", "co", "corp", "dept", "dr", "ed", "eds", "esq", "et al", "etc",
+    "fig", "inc", "jr", "ltd", "mr", "mrs", "ms", "no", "nos", "p", "pp", "prof", "pt", "rev", "sr",
+    "st", "vol", "vols", "vs",
+];
+
+/// Conservative cleanup for archival titles: trims, collapses repeated `!` / `?` (common OCR
+/// noise), and drops a single trailing period unless the last word looks like an abbreviation
+/// or initial (`Vol.`, `Smith, J.`, `U.S.`). Ellipses are left alone.
+pub struct TitleTidyModifier;
+
+impl TitleTidyModifier {
+    fn collapse_repeated_marks(value: &str) -> String {
+        let mut tidy = String::with_capacity(value.len());
+        let mut previous = None;
+        for c in value.chars() {
+            if matches!(c, '!' | '?') && previous == Some(c) {
+                continue;
+            }
+            tidy.push(c);
+            previous = Some(c);
+        }
+        tidy
+    }
+
+    fn ends_with_abbreviation(without_period: &str) -> bool {
+        let last_word = without_period
+            .rsplit(|c: char| c.is_whitespace() || matches!(c, ',' | '(' | '[' | ';' | ':'))
+            .next()
+            .unwrap_or("");
+        if last_word.contains('.') {
+            // Dotted abbreviation such as `U.S.` or `e.g.`
+            return true;
+        }
+        if last_word.chars().count() == 1 && last_word.chars().all(char::is_alphabetic) {
+            // Initial, e.g. `Smith, J.`
+            return true;
+        }
+        let lowered = without_period.to_lowercase();
+        ABBREVIATIONS.iter().any(|abbreviation| {
+            lowered == *abbreviation
+                || lowered.ends_with(&format!(" {}", abbreviation))
+        })
+    }
+
+    /// Tidies one title value; see the type docs for the rules.
+    pub fn tidy(value: &str) -> String {
+        let tidy = Self::collapse_repeated_marks(value.trim());
+        if let Some(without_period) = tidy.strip_suffix('.') {
+            if !without_period.ends_with('.') && !Self::ends_with_abbreviation(without_period) {
+                return without_period.trim_end().to_string();
+            }
+        }
+        tidy
+    }
+}
+
+impl ColumnModifier for TitleTidyModifier {
+    fn modify(&self, value: &str, _row: &RowContext) -> String {
+        Self::tidy(value)
+    }
+
+    fn description(&self) -> &str {
+        "Tidies titles: trims, collapses !!/??, drops a non-abbreviation trailing period"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_trailing_period() {
+        assert_eq!(TitleTidyModifier::tidy("Annual Report."), "Annual Report");
+        assert_eq!(TitleTidyModifier::tidy("  Annual Report. "), "Annual Report");
+    }
+
+    #[test]
+    fn keeps_abbreviations_initials_and_ellipses() {
+        assert_eq!(TitleTidyModifier::tidy("Vol. 2"), "Vol. 2");
+        assert_eq!(TitleTidyModifier::tidy("Newsletter Vol."), "Newsletter Vol.");
+        assert_eq!(TitleTidyModifier::tidy("Letter to Smith, J."), "Letter to Smith, J.");
+        assert_eq!(TitleTidyModifier::tidy("Visit to the U.S."), "Visit to the U.S.");
+        assert_eq!(TitleTidyModifier::tidy("And then..."), "And then...");
+    }
+
+    #[test]
+    fn collapses_repeated_exclamation_and_question_marks() {
+        assert_eq!(TitleTidyModifier::tidy("Welcome!!"), "Welcome!");
+        assert_eq!(TitleTidyModifier::tidy("Who are we??"), "Who are we?");
+        assert_eq!(TitleTidyModifier::tidy("Really?!"), "Really?!");
+    }
+}
@@ -0,0 +1,64 @@
+use crate::csv_modifier::{normalize_cell, ColumnModifier, RowContext};
+
+/// Checks that non-empty cells parse as absolute URLs, optionally repairing values that only
+/// lack a scheme (e.g. `www.example.org/rights`) by prepending `https://`.
+pub struct UrlValidator {
+    pub fix_missing_scheme: bool,
+}
+
+impl UrlValidator {
+    pub const fn new(fix_missing_scheme: bool) -> Self {
+        Self { fix_missing_scheme }
+    }
+
+    fn with_default_scheme(value: &str) -> Option<String> {
+        let candidate = format!("https://{}", value);
+        url::Url::parse(&candidate).ok().map(|_| candidate)
+    }
+
+    fn is_schemeless(value: &str) -> bool {
+        matches!(
+            url::Url::parse(value),
+            Err(url::ParseError::RelativeUrlWithoutBase)
+        )
+    }
+}
+
+impl ColumnModifier for UrlValidator {
+    fn modify(&self, value: &str, _row: &RowContext) -> String {
+        let clean = normalize_cell(value);
+        if self.fix_missing_scheme && Self::is_schemeless(clean) {
+            if let Some(fixed) = Self::with_default_scheme(clean) {
+                return fixed;
+            }
+        }
+        value.to_string()
+    }
+
+    fn description(&self) -> &str {
+        "Validates that cells contain URLs"
+    }
+
+    fn validate(&self, value: &str, _row: &RowContext) -> bool {
+        let clean = normalize_cell(value);
+        if clean.is_empty() || url::Url::parse(clean).is_ok() {
+            return true;
+        }
+
+        self.fix_missing_scheme
+            && Self::is_schemeless(clean)
+            && Self::with_default_scheme(clean).is_some()
+    }
+
+    fn validation_reason(&self, value: &str, _row: &RowContext) -> Option<String> {
+        let clean = normalize_cell(value);
+        match url::Url::parse(clean) {
+            Err(url::ParseError::RelativeUrlWithoutBase) if !self.fix_missing_scheme => Some(
+                "value has no URL scheme (use --fix-missing-scheme to prepend https://)"
+                    .to_string(),
+            ),
+            Err(err) => Some(format!("invalid URL ({})", err)),
+            Ok(_) => None,
+        }
+    }
+}
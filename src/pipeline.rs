@@ -1,15 +1,19 @@
-use crate::csv_modifier::{CsvModifier, ProcessingStats};
-use crate::item_csv_generator::{ItemCsvGenerator, ItemGenerationStats};
+use crate::csv_modifier::{CsvDialect, CsvModifier, OutputFormat, ProcessingStats};
+use crate::item_csv_generator::{
+    ItemBatchReport, ItemCsvGenerator, ItemErrorPolicy, ItemGenerationOptions, ItemGenerationStats,
+};
 use crate::modifiers::{
-    FieldModelModifier, FileExtensionModifier, LanguageModifier, ParentIdModifier,
-    resolve_language_mapping_url,
+    ExecModifier, FieldModelModifier, FileExtensionModifier, LanguageModifier, ParentIdModifier,
+    TitleTidyModifier, UrlValidator, DEFAULT_EXEC_TIMEOUT,
+    DEFAULT_PARENT_ID_COLUMN, resolve_language_mapping_url,
 };
 use crate::{Modifier};
 use anyhow::{Context, Result};
 use std::collections::HashSet;
 use std::fs;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 use tempfile::NamedTempFile;
 
 #[derive(Debug)]
@@ -20,6 +24,72 @@ pub struct ProcessResult {
     pub items_stats: Option<ItemGenerationStats>,
 }
 
+/// Optional processing behaviour layered on top of the `--only-run` / `--ignore-run` selection.
+#[derive(Debug, Clone, Default)]
+pub struct ProcessingOptions {
+    /// Columns whose non-empty cells must parse as URLs (`--validate-urls`).
+    pub validate_urls: Vec<String>,
+    /// Title columns to tidy: trim, collapse `!!` / `??`, drop a non-abbreviation trailing period
+    /// (`--tidy-titles`).
+    pub tidy_titles: Vec<String>,
+    /// `(column, command)` pairs whose command output replaces the cell (`--exec-modifier`).
+    /// Rejected unless `allow_exec` is set.
+    pub exec_modifiers: Vec<(String, String)>,
+    /// Explicit opt-in to running `exec_modifiers` commands (`--allow-exec`).
+    pub allow_exec: bool,
+    /// Limit per command invocation (`--exec-timeout`); `None` uses [`DEFAULT_EXEC_TIMEOUT`].
+    pub exec_timeout: Option<Duration>,
+    /// Prepend `https://` to schemeless values in `validate_urls` columns (`--fix-missing-scheme`).
+    pub fix_missing_scheme: bool,
+    /// Where to record the kept row for each duplicated accessIdentifier (`--dedup-kept-output`).
+    /// Relative paths are placed under the output directory, like other outputs.
+    pub dedup_kept_output: Option<String>,
+    /// Keep at most this many accessIdentifiers in memory for duplicate detection, spilling the
+    /// rest to a temporary on-disk index (`--dedup-memory-cap`).
+    pub dedup_memory_cap: Option<usize>,
+    /// Where to write `source_row,original_accessIdentifier,parent_id,file` for every written row
+    /// (`--crosswalk`). Relative paths are placed under the output directory.
+    pub crosswalk: Option<String>,
+    /// For Google Sheets input, fetch only the worksheet tab with this gid (`--gid`) instead of
+    /// the first tab.
+    pub gid: Option<String>,
+    /// For Google Sheets input, fetch and concatenate every worksheet tab (`--all-tabs`).
+    pub all_tabs: bool,
+    /// With `all_tabs` or a sheet batch, how many tabs or sheets to fetch at once
+    /// (`--fetch-concurrency`; 0 or 1 is sequential).
+    pub fetch_concurrency: usize,
+    /// Write the processed CSV with a UTF-8 BOM and CRLF line endings (`--excel-friendly`).
+    pub excel_friendly: bool,
+    /// Write a sorted, quote-all, LF-terminated form for version control (`--canonical`).
+    pub canonical: bool,
+    /// Re-read input and output and fail if untouched columns changed (`--verify-passthrough`).
+    pub verify_passthrough: bool,
+    /// Column the parent-id modifier writes to (`--parent-id-column`, default `parent_id`).
+    pub parent_id_column: Option<String>,
+    /// `(from, to)` relabels applied to derived `field_model` values (`--rename-model`).
+    pub model_renames: Vec<(String, String)>,
+    /// Serialization of the processed rows (`--output-format`). Default output names use a
+    /// `.jsonl` extension for JSON lines, and item generation requires CSV.
+    pub output_format: OutputFormat,
+    /// Copy the input `file` value into an appended `file_original` column
+    /// (`--keep-original-file-column`).
+    pub keep_original_file_column: bool,
+    /// Columns passed through exactly as read; changes that would apply are only logged
+    /// (`--protect-columns`).
+    pub protect_columns: Vec<String>,
+    /// Keep `_00` / `_000` container rows instead of skipping them (`--keep-containers`).
+    pub keep_containers: bool,
+    /// Fail when a `file` suffix disagrees with `file_extension` (`--strict`).
+    pub strict: bool,
+    /// Quoting of the input CSV (`--quote-char`, `--escape-char`).
+    pub input_dialect: CsvDialect,
+    /// Rejoin titles split into two fields by an unquoted comma (`--repair-unquoted-commas`).
+    pub repair_unquoted_commas: bool,
+    /// Write the processed CSV in `input_dialect` instead of standard CSV (`--keep-dialect`).
+    /// Item generation reads standard CSV only.
+    pub keep_dialect: bool,
+}
+
 fn determine_modifiers_to_run(
     only_run: &[Modifier],
     ignore_run: &[Modifier],
@@ -50,18 +120,73 @@ fn create_modifier(
     only_run: &[Modifier],
     ignore_run: &[Modifier],
     language_url: Option<&str>,
+    processing_options: &ProcessingOptions,
+    output_dir: Option<&str>,
 ) -> Result<CsvModifier> {
     let active_modifiers = determine_modifiers_to_run(only_run, ignore_run);
-    let mut modifier = CsvModifier::new();
+    let parent_id_column = processing_options
+        .parent_id_column
+        .as_deref()
+        .unwrap_or(DEFAULT_PARENT_ID_COLUMN);
+    let mut modifier = CsvModifier::new().with_parent_id_column(parent_id_column);
 
-    // Note: CsvModifier::new() always includes the accessIdentifier validator.
-    // This wrapper only toggles the additional column modifiers enabled by the CLI.
-    if active_modifiers.is_empty() {
-        return Ok(modifier);
+    if processing_options.excel_friendly {
+        modifier = modifier.with_excel_friendly_output();
+    }
+
+    if processing_options.canonical {
+        modifier = modifier.with_canonical_output();
+    }
+    if processing_options.verify_passthrough {
+        modifier = modifier.with_passthrough_verification();
+    }
+    if processing_options.keep_original_file_column {
+        modifier = modifier.with_original_file_column();
+    }
+    if !processing_options.protect_columns.is_empty() {
+        modifier =
+            modifier.with_protected_columns(processing_options.protect_columns.iter().cloned());
+    }
+    if processing_options.keep_containers {
+        modifier = modifier.with_containers_kept();
+    }
+    if processing_options.strict {
+        modifier = modifier.with_strict_extension_check();
+    }
+    if let Some(cap) = processing_options.dedup_memory_cap {
+        modifier = modifier.with_dedup_memory_cap(cap);
+    }
+    if processing_options.repair_unquoted_commas {
+        modifier = modifier.with_unquoted_comma_repair();
+    }
+    if processing_options.output_format != OutputFormat::Csv {
+        modifier = modifier.with_output_format(processing_options.output_format);
+    }
+    if processing_options.input_dialect != CsvDialect::default() {
+        modifier = modifier.with_input_dialect(processing_options.input_dialect);
+        if processing_options.keep_dialect {
+            modifier = modifier.with_output_dialect(processing_options.input_dialect);
+        }
+    }
+
+    if let Some(path) = processing_options.dedup_kept_output.as_deref() {
+        modifier = modifier.with_dedup_kept_output(&finalize_output_path(path, output_dir)?);
+    }
+    if let Some(path) = processing_options.crosswalk.as_deref() {
+        modifier = modifier.with_crosswalk_output(&finalize_output_path(path, output_dir)?);
+    }
+
+    if !processing_options.exec_modifiers.is_empty() && !processing_options.allow_exec {
+        anyhow::bail!(
+            "--exec-modifier runs arbitrary commands with your permissions; pass --allow-exec to enable it"
+        );
     }
 
+    // Note: CsvModifier::new() always includes the accessIdentifier validator.
+    // The built-in modifiers replace any defaults on their columns; the opt-in modifiers below
+    // are chained after them so they compose instead of being overwritten.
     if active_modifiers.contains(&Modifier::ParentId) {
-        modifier = modifier.add_column_modifier("parent_id", ParentIdModifier);
+        modifier = modifier.add_column_modifier(parent_id_column, ParentIdModifier);
     }
 
     if active_modifiers.contains(&Modifier::FileExtension) {
@@ -69,7 +194,8 @@ fn create_modifier(
     }
 
     if active_modifiers.contains(&Modifier::FieldModel) {
-        let field_model_modifier = FieldModelModifier::from_default_config()?;
+        let field_model_modifier = FieldModelModifier::from_default_config()?
+            .with_model_renames(processing_options.model_renames.iter().cloned());
         modifier = modifier.add_column_modifier("field_model", field_model_modifier);
     }
 
@@ -79,6 +205,22 @@ fn create_modifier(
         modifier = modifier.add_column_modifier("field_language", language_modifier);
     }
 
+    for column in &processing_options.validate_urls {
+        modifier = modifier.chain_column_modifier(
+            column,
+            UrlValidator::new(processing_options.fix_missing_scheme),
+        );
+    }
+
+    for column in &processing_options.tidy_titles {
+        modifier = modifier.chain_column_modifier(column, TitleTidyModifier);
+    }
+
+    let exec_timeout = processing_options.exec_timeout.unwrap_or(DEFAULT_EXEC_TIMEOUT);
+    for (column, command) in &processing_options.exec_modifiers {
+        modifier = modifier.chain_column_modifier(column, ExecModifier::new(command, exec_timeout));
+    }
+
     Ok(modifier)
 }
 
@@ -100,6 +242,31 @@ fn generate_output_filename(input: &str) -> String {
     }
 }
 
+/// Swaps the extension of a default output name to match `format`; explicit names are kept.
+fn default_name_for_format(path: String, explicit: bool, format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Jsonl if !explicit => Path::new(&path)
+            .with_extension("jsonl")
+            .to_string_lossy()
+            .into_owned(),
+        _ => path,
+    }
+}
+
+/// Items are generated by re-reading the processed output, which must therefore be standard CSV.
+fn ensure_items_can_read_output(full: bool, options: &ProcessingOptions) -> Result<()> {
+    if !full {
+        return Ok(());
+    }
+    if options.output_format != OutputFormat::Csv {
+        anyhow::bail!("Generating items requires CSV output; drop --output-format or --full");
+    }
+    if options.keep_dialect && options.input_dialect != CsvDialect::default() {
+        anyhow::bail!("Generating items requires standard CSV output; drop --keep-dialect or --full");
+    }
+    Ok(())
+}
+
 fn generate_sheets_output_filename() -> String {
     "sheets-output-modified.csv".to_string()
 }
@@ -205,18 +372,143 @@ fn finalize_output_path(path: &str, output_dir: Option<&str>) -> Result<String>
     Ok(path.to_string())
 }
 
+/// Resolves a path for comparison without requiring it to exist: existing files (or their
+/// existing parent directories) are canonicalized so `./a.csv` and `dir/../a.csv` compare equal.
+fn comparable_path(path: &str) -> PathBuf {
+    let candidate = Path::new(path);
+    if let Ok(canonical) = fs::canonicalize(candidate) {
+        return canonical;
+    }
+
+    if let (Some(parent), Some(file_name)) = (candidate.parent(), candidate.file_name()) {
+        let parent = if parent.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            parent
+        };
+        if let Ok(canonical_parent) = fs::canonicalize(parent) {
+            return canonical_parent.join(file_name);
+        }
+    }
+
+    std::path::absolute(candidate).unwrap_or_else(|_| candidate.to_path_buf())
+}
+
+/// Checks a batch of `(input, output)` pairs before anything is written.
+///
+/// Errors, listing every offending pair, when two pairs would write the same output path or when
+/// an output would overwrite any input of the batch.
+pub fn check_output_collisions(pairs: &[(&str, &str)]) -> Result<()> {
+    let inputs: Vec<(&str, PathBuf)> = pairs
+        .iter()
+        .map(|&(input, _)| (input, comparable_path(input)))
+        .collect();
+    let outputs: Vec<(&str, &str, PathBuf)> = pairs
+        .iter()
+        .map(|&(input, output)| (input, output, comparable_path(output)))
+        .collect();
+
+    let mut problems = Vec::new();
+
+    for (i, (input_a, output_a, resolved_a)) in outputs.iter().enumerate() {
+        for (input_b, _, resolved_b) in outputs.iter().skip(i + 1) {
+            if resolved_a == resolved_b {
+                problems.push(format!(
+                    "outputs for '{}' and '{}' both resolve to '{}'",
+                    input_a, input_b, output_a
+                ));
+            }
+        }
+
+        for (input, resolved_input) in &inputs {
+            if resolved_a == resolved_input {
+                problems.push(format!(
+                    "output '{}' for '{}' would overwrite input '{}'",
+                    output_a, input_a, input
+                ));
+            }
+        }
+    }
+
+    problems.dedup();
+
+    if !problems.is_empty() {
+        anyhow::bail!("Output path collision detected:\n  - {}", problems.join("\n  - "));
+    }
+
+    Ok(())
+}
+
+/// Files a processing run writes besides the processed and items outputs: `--dedup-kept-output`
+/// and `--crosswalk` (resolved as [`create_modifier`] does) and, with `full`, `--items-ledger`.
+fn side_output_paths(
+    processing_options: &ProcessingOptions,
+    full: bool,
+    items_options: &ItemGenerationOptions,
+    output_dir: Option<&str>,
+) -> Result<Vec<String>> {
+    let mut paths = Vec::new();
+    for path in [
+        processing_options.dedup_kept_output.as_deref(),
+        processing_options.crosswalk.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        paths.push(finalize_output_path(path, output_dir)?);
+    }
+    if full {
+        paths.extend(items_options.ledger.iter().cloned());
+    }
+    Ok(paths)
+}
+
 pub fn generate_items_from_path(
     input_path: &str,
     output_path: &str,
     node: Option<&str>,
+    items_options: &ItemGenerationOptions,
 ) -> Result<ItemGenerationStats> {
-    ItemCsvGenerator::generate(input_path, output_path, node)
+    ItemCsvGenerator::generate_with_options(input_path, output_path, node, items_options)
+}
+
+/// Generate items for several local files, each written to `<input-stem>-items.csv` (under
+/// `output_dir` when set). `policy` decides whether a failing file stops the batch.
+pub fn generate_items_from_paths(
+    input_paths: &[String],
+    output_dir: Option<&str>,
+    node: Option<&str>,
+    items_options: &ItemGenerationOptions,
+    policy: ItemErrorPolicy,
+) -> Result<ItemBatchReport> {
+    let output_paths = input_paths
+        .iter()
+        .map(|input| {
+            let default_path = determine_items_output_path(input, None, None)?;
+            match (output_dir, Path::new(&default_path).file_name()) {
+                (Some(dir), Some(file_name)) => {
+                    finalize_output_path(&file_name.to_string_lossy(), Some(dir))
+                }
+                _ => Ok(default_path),
+            }
+        })
+        .collect::<Result<Vec<String>>>()?;
+
+    let jobs: Vec<(&str, &str)> = input_paths
+        .iter()
+        .map(String::as_str)
+        .zip(output_paths.iter().map(String::as_str))
+        .collect();
+    check_output_collisions(&jobs)?;
+
+    ItemCsvGenerator::generate_batch(&jobs, node, items_options, policy)
 }
 
 pub fn generate_items_from_url(
     url: &str,
     output_path: &str,
     node: Option<&str>,
+    items_options: &ItemGenerationOptions,
 ) -> Result<ItemGenerationStats> {
     let csv_data = CsvModifier::fetch_google_sheets_csv(url)?;
 
@@ -228,7 +520,7 @@ pub fn generate_items_from_url(
         .to_str()
         .ok_or_else(|| anyhow::anyhow!("Temporary file path contains invalid UTF-8"))?;
 
-    ItemCsvGenerator::generate(path_str, output_path, node)
+    ItemCsvGenerator::generate_with_options(path_str, output_path, node, items_options)
 }
 
 /// Library equivalent of the CLI `generate-items` subcommand.
@@ -240,12 +532,21 @@ pub fn generate_items_from_source(
     url: Option<&str>,
     output_path: Option<&str>,
     node: Option<&str>,
+    items_options: &ItemGenerationOptions,
 ) -> Result<ItemGenerationStats> {
     let output_path = output_path.unwrap_or("items.csv");
 
+    if let Some(source) = input_path.or(url) {
+        let mut planned_outputs = vec![(source, output_path)];
+        if let Some(ledger) = items_options.ledger.as_deref() {
+            planned_outputs.push((source, ledger));
+        }
+        check_output_collisions(&planned_outputs)?;
+    }
+
     match (input_path, url) {
-        (Some(path), None) => generate_items_from_path(path, output_path, node),
-        (None, Some(link)) => generate_items_from_url(link, output_path, node),
+        (Some(path), None) => generate_items_from_path(path, output_path, node, items_options),
+        (None, Some(link)) => generate_items_from_url(link, output_path, node, items_options),
         (Some(_), Some(_)) => anyhow::bail!("Specify either input_path or url, not both"),
         (None, None) => anyhow::bail!("No input provided. Provide input_path or url."),
     }
@@ -259,33 +560,58 @@ pub fn process_csv_and_maybe_generate_items(
     only_run: &[Modifier],
     ignore_run: &[Modifier],
     language_url: Option<&str>,
+    processing_options: &ProcessingOptions,
     full: bool,
     items_output: Option<&str>,
     node: Option<&str>,
+    items_options: &ItemGenerationOptions,
 ) -> Result<ProcessResult> {
     if !Path::new(input_path).exists() {
         anyhow::bail!("Input file does not exist: {}", input_path);
     }
+    ensure_items_can_read_output(full, processing_options)?;
 
-    let processed_output_path = determine_processed_output_path(
-        input_path,
-        explicit_output,
-        output_dir,
-    )?;
-
-    let modifier = create_modifier(only_run, ignore_run, language_url)?;
-    let processing_stats = modifier.process_file(input_path, &processed_output_path)?;
+    let processed_output_path = default_name_for_format(
+        determine_processed_output_path(input_path, explicit_output, output_dir)?,
+        explicit_output.is_some(),
+        processing_options.output_format,
+    );
 
-    let (items_output_path, items_stats) = if full {
-        let items_output_path = determine_items_output_path(
+    let items_output_path = if full {
+        Some(determine_items_output_path(
             &processed_output_path,
             items_output,
             output_dir,
-        )?;
-        let stats = generate_items_from_path(&processed_output_path, &items_output_path, node)?;
-        (Some(items_output_path), Some(stats))
+        )?)
     } else {
-        (None, None)
+        None
+    };
+
+    let side_outputs = side_output_paths(processing_options, full, items_options, output_dir)?;
+    let mut planned_outputs = vec![(input_path, processed_output_path.as_str())];
+    if let Some(items_path) = items_output_path.as_deref() {
+        planned_outputs.push((input_path, items_path));
+    }
+    planned_outputs.extend(side_outputs.iter().map(|path| (input_path, path.as_str())));
+    check_output_collisions(&planned_outputs)?;
+
+    let modifier = create_modifier(
+        only_run,
+        ignore_run,
+        language_url,
+        processing_options,
+        output_dir,
+    )?;
+    let processing_stats = modifier.process_file(input_path, &processed_output_path)?;
+
+    let items_stats = match items_output_path.as_deref() {
+        Some(items_path) => Some(generate_items_from_path(
+            &processed_output_path,
+            items_path,
+            node,
+            items_options,
+        )?),
+        None => None,
     };
 
     Ok(ProcessResult {
@@ -304,28 +630,65 @@ pub fn process_google_sheets_and_maybe_generate_items(
     only_run: &[Modifier],
     ignore_run: &[Modifier],
     language_url: Option<&str>,
+    processing_options: &ProcessingOptions,
     full: bool,
     items_output: Option<&str>,
     node: Option<&str>,
+    items_options: &ItemGenerationOptions,
 ) -> Result<ProcessResult> {
-    let processed_output_path = determine_processed_output_path_for_sheets(
-        explicit_output,
-        output_dir,
-    )?;
+    ensure_items_can_read_output(full, processing_options)?;
 
-    let modifier = create_modifier(only_run, ignore_run, language_url)?;
-    let processing_stats = modifier.process_google_sheets(url, &processed_output_path)?;
+    let processed_output_path = default_name_for_format(
+        determine_processed_output_path_for_sheets(explicit_output, output_dir)?,
+        explicit_output.is_some(),
+        processing_options.output_format,
+    );
 
-    let (items_output_path, items_stats) = if full {
-        let items_output_path = determine_items_output_path(
+    let items_output_path = if full {
+        Some(determine_items_output_path(
             &processed_output_path,
             items_output,
             output_dir,
-        )?;
-        let stats = generate_items_from_path(&processed_output_path, &items_output_path, node)?;
-        (Some(items_output_path), Some(stats))
+        )?)
     } else {
-        (None, None)
+        None
+    };
+
+    let side_outputs = side_output_paths(processing_options, full, items_options, output_dir)?;
+    let mut planned_outputs = vec![(url, processed_output_path.as_str())];
+    if let Some(items_path) = items_output_path.as_deref() {
+        planned_outputs.push((url, items_path));
+    }
+    planned_outputs.extend(side_outputs.iter().map(|path| (url, path.as_str())));
+    check_output_collisions(&planned_outputs)?;
+
+    let modifier = create_modifier(
+        only_run,
+        ignore_run,
+        language_url,
+        processing_options,
+        output_dir,
+    )?;
+    let processing_stats = if processing_options.all_tabs {
+        modifier.process_google_sheets_all_tabs(
+            url,
+            processing_options.fetch_concurrency,
+            &processed_output_path,
+        )?
+    } else if let Some(gid) = processing_options.gid.as_deref() {
+        modifier.process_google_sheets_tab(url, gid, &processed_output_path)?
+    } else {
+        modifier.process_google_sheets(url, &processed_output_path)?
+    };
+
+    let items_stats = match items_output_path.as_deref() {
+        Some(items_path) => Some(generate_items_from_path(
+            &processed_output_path,
+            items_path,
+            node,
+            items_options,
+        )?),
+        None => None,
     };
 
     Ok(ProcessResult {
@@ -336,6 +699,140 @@ pub fn process_google_sheets_and_maybe_generate_items(
     })
 }
 
+/// Reads a `--url-file`: one Google Sheets URL per line; blank lines and `#` comments are skipped.
+pub fn read_url_file(path: &str) -> Result<Vec<String>> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read URL file: {}", path))?;
+    let urls: Vec<String> = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect();
+    if urls.is_empty() {
+        anyhow::bail!("URL file {} lists no URLs", path);
+    }
+    Ok(urls)
+}
+
+/// Process several Google Sheets, each written to `<spreadsheet-id>-modified.csv` (under
+/// `output_dir`) and, with `full`, to `<spreadsheet-id>-modified-items.csv` beside it.
+///
+/// Up to `processing_options.fetch_concurrency` sheets are fetched at once, each with the same
+/// HTTP settings as a single `--url` fetch. The fetched sheets are then processed one at a time in
+/// the order of `urls`; the first failed fetch or processing error stops the batch.
+#[allow(clippy::too_many_arguments)]
+pub fn process_google_sheets_batch_and_maybe_generate_items(
+    urls: &[String],
+    output_dir: Option<&str>,
+    only_run: &[Modifier],
+    ignore_run: &[Modifier],
+    language_url: Option<&str>,
+    processing_options: &ProcessingOptions,
+    full: bool,
+    node: Option<&str>,
+    items_options: &ItemGenerationOptions,
+) -> Result<Vec<ProcessResult>> {
+    process_sheet_batch_with(
+        urls,
+        output_dir,
+        only_run,
+        ignore_run,
+        language_url,
+        processing_options,
+        full,
+        node,
+        items_options,
+        CsvModifier::fetch_google_sheets_csv_batch,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn process_sheet_batch_with<F>(
+    urls: &[String],
+    output_dir: Option<&str>,
+    only_run: &[Modifier],
+    ignore_run: &[Modifier],
+    language_url: Option<&str>,
+    processing_options: &ProcessingOptions,
+    full: bool,
+    node: Option<&str>,
+    items_options: &ItemGenerationOptions,
+    fetch_batch: F,
+) -> Result<Vec<ProcessResult>>
+where
+    F: FnOnce(&[String], usize) -> Vec<Result<String>>,
+{
+    ensure_items_can_read_output(full, processing_options)?;
+    if processing_options.crosswalk.is_some() || processing_options.dedup_kept_output.is_some() {
+        anyhow::bail!("--crosswalk and --dedup-kept-output write a single file; process sheets one at a time to use them");
+    }
+    if full && items_options.ledger.is_some() {
+        anyhow::bail!("--items-ledger tracks a single items file; process sheets one at a time to use it");
+    }
+
+    let mut outputs = Vec::with_capacity(urls.len());
+    for url in urls {
+        let sheet_id = CsvModifier::google_sheets_id(url)?;
+        let processed_output_path = default_name_for_format(
+            finalize_output_path(&format!("{}-modified.csv", sheet_id), output_dir)?,
+            false,
+            processing_options.output_format,
+        );
+        let items_output_path = if full {
+            Some(determine_items_output_path(&processed_output_path, None, output_dir)?)
+        } else {
+            None
+        };
+        outputs.push((processed_output_path, items_output_path));
+    }
+
+    let mut planned_outputs = Vec::new();
+    for (url, (processed, items)) in urls.iter().zip(&outputs) {
+        planned_outputs.push((url.as_str(), processed.as_str()));
+        if let Some(items) = items.as_deref() {
+            planned_outputs.push((url.as_str(), items));
+        }
+    }
+    check_output_collisions(&planned_outputs)?;
+
+    let modifier = create_modifier(
+        only_run,
+        ignore_run,
+        language_url,
+        processing_options,
+        output_dir,
+    )?;
+
+    let fetched = fetch_batch(urls, processing_options.fetch_concurrency);
+    let mut results = Vec::with_capacity(urls.len());
+    for ((url, csv_data), (processed_output_path, items_output_path)) in
+        urls.iter().zip(fetched).zip(outputs)
+    {
+        let csv_data = csv_data.with_context(|| format!("Failed to fetch {}", url))?;
+        let processing_stats = modifier
+            .process_csv_data(&csv_data, &processed_output_path)
+            .with_context(|| format!("Failed to process {}", url))?;
+        let items_stats = match items_output_path.as_deref() {
+            Some(items_path) => Some(generate_items_from_path(
+                &processed_output_path,
+                items_path,
+                node,
+                items_options,
+            )?),
+            None => None,
+        };
+        results.push(ProcessResult {
+            processed_output_path,
+            processing_stats,
+            items_output_path,
+            items_stats,
+        });
+    }
+
+    Ok(results)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -377,5 +874,307 @@ mod tests {
         assert!(Path::new(&items).parent().unwrap().exists());
         Ok(())
     }
-}
 
+    #[test]
+    fn collision_check_rejects_outputs_resolving_to_same_path() -> Result<()> {
+        let temp = tempdir()?;
+        let output_dir = temp.path().join("outputs");
+        let output_dir = output_dir.to_str().unwrap();
+
+        let first_input = temp.path().join("box1").join("records.csv");
+        let second_input = temp.path().join("box2").join("records.csv");
+        let first_input = first_input.to_str().unwrap();
+        let second_input = second_input.to_str().unwrap();
+
+        let first_output = determine_processed_output_path(first_input, None, Some(output_dir))?;
+        let second_output = determine_processed_output_path(second_input, None, Some(output_dir))?;
+
+        let err = check_output_collisions(&[
+            (first_input, first_output.as_str()),
+            (second_input, second_output.as_str()),
+        ])
+        .unwrap_err()
+        .to_string();
+
+        assert!(err.contains(first_input), "{}", err);
+        assert!(err.contains(second_input), "{}", err);
+        assert!(err.contains("records-modified.csv"), "{}", err);
+        Ok(())
+    }
+
+    #[test]
+    fn collision_check_rejects_output_overwriting_input() -> Result<()> {
+        let temp = tempdir()?;
+        let input = temp.path().join("data.csv");
+        fs::write(&input, b"input")?;
+        let input = input.to_str().unwrap();
+        let same_file = format!("{}/./data.csv", temp.path().display());
+
+        let err = check_output_collisions(&[(input, same_file.as_str())])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("would overwrite input"), "{}", err);
+
+        let distinct = temp.path().join("data-modified.csv");
+        check_output_collisions(&[(input, distinct.to_str().unwrap())])?;
+        Ok(())
+    }
+
+    const SHEET_A: &str = "https://docs.google.com/spreadsheets/d/sheetAAA/edit#gid=0";
+    const SHEET_B: &str = "https://docs.google.com/spreadsheets/d/sheetBBB/edit";
+
+    #[test]
+    fn url_file_skips_blank_lines_and_comments() -> Result<()> {
+        let temp = tempdir()?;
+        let path = temp.path().join("urls.txt");
+        fs::write(&path, format!("# box 1\n{}\n\n  {}  \n", SHEET_A, SHEET_B))?;
+
+        let urls = read_url_file(path.to_str().unwrap())?;
+        assert_eq!(urls, vec![SHEET_A.to_string(), SHEET_B.to_string()]);
+        Ok(())
+    }
+
+    #[test]
+    fn sheet_batch_fetches_every_url_and_processes_each_in_order() -> Result<()> {
+        let temp = tempdir()?;
+        let output_dir = temp.path().to_str().unwrap();
+        let urls = vec![SHEET_A.to_string(), SHEET_B.to_string()];
+        let fetched_urls = std::sync::Mutex::new(Vec::new());
+
+        let results = process_sheet_batch_with(
+            &urls,
+            Some(output_dir),
+            &[Modifier::ParentId],
+            &[],
+            None,
+            &ProcessingOptions {
+                fetch_concurrency: 2,
+                ..Default::default()
+            },
+            false,
+            None,
+            &ItemGenerationOptions::default(),
+            |urls, concurrency| {
+                assert_eq!(concurrency, 2);
+                crate::google_sheets::fetch_concurrently(urls, concurrency, |url| {
+                    fetched_urls.lock().unwrap().push(url.clone());
+                    let id = CsvModifier::google_sheets_id(url)?;
+                    Ok(format!(
+                        "accessIdentifier,title\n{id}_001_001,One\n{id}_001_002,Two\n"
+                    ))
+                })
+            },
+        )?;
+
+        let mut fetched_urls = fetched_urls.into_inner().unwrap();
+        fetched_urls.sort();
+        assert_eq!(fetched_urls, urls);
+
+        assert_eq!(results.len(), 2);
+        for (result, id) in results.iter().zip(["sheetAAA", "sheetBBB"]) {
+            assert!(
+                result.processed_output_path.ends_with(&format!("{}-modified.csv", id)),
+                "{}",
+                result.processed_output_path
+            );
+            assert_eq!(result.processing_stats.total_rows, 2);
+            let written = fs::read_to_string(&result.processed_output_path)?;
+            assert!(written.contains(&format!("{}_001_001", id)), "{}", written);
+            assert!(written.contains(&format!("{}_001_002", id)), "{}", written);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn sheet_batch_stops_at_first_failed_fetch() -> Result<()> {
+        let temp = tempdir()?;
+        let output_dir = temp.path().to_str().unwrap();
+        let urls = vec![SHEET_A.to_string(), SHEET_B.to_string()];
+
+        let err = process_sheet_batch_with(
+            &urls,
+            Some(output_dir),
+            &[Modifier::ParentId],
+            &[],
+            None,
+            &ProcessingOptions::default(),
+            false,
+            None,
+            &ItemGenerationOptions::default(),
+            |urls, _| {
+                urls.iter()
+                    .map(|url| {
+                        if url == SHEET_A {
+                            Err(anyhow::anyhow!("HTTP error 404"))
+                        } else {
+                            Ok("accessIdentifier\nsheetBBB_001_001\n".to_string())
+                        }
+                    })
+                    .collect()
+            },
+        )
+        .unwrap_err();
+
+        assert!(format!("{:#}", err).contains(SHEET_A), "{:#}", err);
+        assert!(!temp.path().join("sheetBBB-modified.csv").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn sheet_batch_rejects_the_same_sheet_listed_twice() {
+        let temp = tempdir().unwrap();
+        let urls = vec![SHEET_A.to_string(), SHEET_A.replace("#gid=0", "")];
+
+        let err = process_sheet_batch_with(
+            &urls,
+            Some(temp.path().to_str().unwrap()),
+            &[Modifier::ParentId],
+            &[],
+            None,
+            &ProcessingOptions::default(),
+            false,
+            None,
+            &ItemGenerationOptions::default(),
+            |_, _| panic!("nothing should be fetched"),
+        )
+        .unwrap_err()
+        .to_string();
+
+        assert!(err.contains("sheetAAA-modified.csv"), "{}", err);
+    }
+
+    fn process_local(
+        input: &str,
+        output_dir: &str,
+        processing_options: &ProcessingOptions,
+        full: bool,
+        items_options: &ItemGenerationOptions,
+    ) -> Result<ProcessResult> {
+        process_csv_and_maybe_generate_items(
+            input,
+            None,
+            Some(output_dir),
+            &[Modifier::ParentId],
+            &[],
+            None,
+            processing_options,
+            full,
+            None,
+            None,
+            items_options,
+        )
+    }
+
+    #[test]
+    fn collision_check_covers_crosswalk_overwriting_input() -> Result<()> {
+        let temp = tempdir()?;
+        let input = temp.path().join("data.csv");
+        fs::write(&input, "accessIdentifier\nabc_001_001\n")?;
+        let input = input.to_str().unwrap();
+
+        let err = process_local(
+            input,
+            temp.path().to_str().unwrap(),
+            &ProcessingOptions {
+                crosswalk: Some("data.csv".to_string()),
+                ..Default::default()
+            },
+            false,
+            &ItemGenerationOptions::default(),
+        )
+        .unwrap_err()
+        .to_string();
+
+        assert!(err.contains("would overwrite input"), "{}", err);
+        assert_eq!(fs::read_to_string(input)?, "accessIdentifier\nabc_001_001\n");
+        Ok(())
+    }
+
+    #[test]
+    fn collision_check_covers_dedup_kept_output() -> Result<()> {
+        let temp = tempdir()?;
+        let input = temp.path().join("data.csv");
+        fs::write(&input, "accessIdentifier\nabc_001_001\n")?;
+
+        let err = process_local(
+            input.to_str().unwrap(),
+            temp.path().to_str().unwrap(),
+            &ProcessingOptions {
+                dedup_kept_output: Some("data-modified.csv".to_string()),
+                ..Default::default()
+            },
+            false,
+            &ItemGenerationOptions::default(),
+        )
+        .unwrap_err()
+        .to_string();
+
+        assert!(err.contains("both resolve to"), "{}", err);
+        assert!(!temp.path().join("data-modified.csv").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn collision_check_covers_items_ledger() -> Result<()> {
+        let temp = tempdir()?;
+        let input = temp.path().join("data.csv");
+        fs::write(&input, "accessIdentifier\nabc_001_001\n")?;
+        let items = temp.path().join("data-modified-items.csv");
+        let items_options = ItemGenerationOptions {
+            ledger: Some(items.to_str().unwrap().to_string()),
+            ..Default::default()
+        };
+
+        let err = process_local(
+            input.to_str().unwrap(),
+            temp.path().to_str().unwrap(),
+            &ProcessingOptions::default(),
+            true,
+            &items_options,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("both resolve to"), "{}", err);
+
+        let err = generate_items_from_source(
+            Some(input.to_str().unwrap()),
+            None,
+            Some(items.to_str().unwrap()),
+            None,
+            &items_options,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("both resolve to"), "{}", err);
+        assert!(!items.exists());
+        Ok(())
+    }
+
+    #[test]
+    fn collision_check_covers_google_sheets_outputs() -> Result<()> {
+        let temp = tempdir()?;
+        let output = temp.path().join("sheet.csv");
+        let output = output.to_str().unwrap();
+
+        // Fails on the planned paths, before anything is fetched.
+        let err = process_google_sheets_and_maybe_generate_items(
+            SHEET_A,
+            Some(output),
+            None,
+            &[Modifier::ParentId],
+            &[],
+            None,
+            &ProcessingOptions::default(),
+            true,
+            Some(output),
+            None,
+            &ItemGenerationOptions::default(),
+        )
+        .unwrap_err()
+        .to_string();
+
+        assert!(err.contains("both resolve to"), "{}", err);
+        assert!(!Path::new(output).exists());
+        Ok(())
+    }
+}
@@ -0,0 +1,162 @@
+use anyhow::{Context, Result};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Quiet period after the last change before reprocessing; editors often write a file in bursts.
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// How often the loop wakes up to notice Ctrl-C while no changes arrive.
+const STOP_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Calls `reprocess` each time `path` changes on disk, until Ctrl-C.
+///
+/// The file's directory is watched (not the file itself) so editors that save by writing a new
+/// file and renaming it over the old one are still picked up. Bursts of changes closer together
+/// than `debounce` trigger a single call.
+pub fn watch_file<F>(path: &str, debounce: Duration, reprocess: F) -> Result<()>
+where
+    F: FnMut(),
+{
+    let target = fs::canonicalize(path)
+        .with_context(|| format!("Failed to resolve watched file: {}", path))?;
+    let watch_dir = target
+        .parent()
+        .context("Watched file has no parent directory")?
+        .to_path_buf();
+    let file_name = target
+        .file_name()
+        .context("Watched path has no file name")?
+        .to_os_string();
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
+        if let Ok(event) = res {
+            if touches_file(&event, &file_name) {
+                let _ = tx.send(());
+            }
+        }
+    })
+    .context("Failed to start file watcher")?;
+    watcher
+        .watch(&watch_dir, RecursiveMode::NonRecursive)
+        .with_context(|| format!("Failed to watch {}", watch_dir.display()))?;
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let stop_flag = Arc::clone(&stop);
+    ctrlc::set_handler(move || stop_flag.store(true, Ordering::SeqCst))
+        .context("Failed to install Ctrl-C handler")?;
+
+    run_watch_loop(&rx, debounce, &stop, reprocess);
+    Ok(())
+}
+
+/// Whether `event` creates or modifies the watched file (matched by name within its directory).
+fn touches_file(event: &Event, file_name: &std::ffi::OsStr) -> bool {
+    matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+        && event
+            .paths
+            .iter()
+            .any(|changed| changed.file_name() == Some(file_name))
+}
+
+/// Drives reprocessing from a stream of change notifications; returns the number of runs.
+///
+/// After a change, further changes are absorbed until none arrives for `debounce`, then
+/// `reprocess` runs once. Returns when `stop` is set or the sender is dropped.
+pub(crate) fn run_watch_loop<F>(
+    changes: &Receiver<()>,
+    debounce: Duration,
+    stop: &AtomicBool,
+    mut reprocess: F,
+) -> usize
+where
+    F: FnMut(),
+{
+    let mut runs = 0;
+
+    while !stop.load(Ordering::SeqCst) {
+        match changes.recv_timeout(STOP_POLL_INTERVAL) {
+            Ok(()) => {
+                let mut disconnected = false;
+                loop {
+                    match changes.recv_timeout(debounce) {
+                        Ok(()) => continue,
+                        Err(RecvTimeoutError::Timeout) => break,
+                        Err(RecvTimeoutError::Disconnected) => {
+                            disconnected = true;
+                            break;
+                        }
+                    }
+                }
+
+                if stop.load(Ordering::SeqCst) {
+                    break;
+                }
+                reprocess();
+                runs += 1;
+
+                if disconnected {
+                    break;
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn burst_of_changes_triggers_one_reprocess() {
+        let (tx, rx) = mpsc::channel();
+        for _ in 0..5 {
+            tx.send(()).unwrap();
+        }
+        drop(tx);
+
+        let stop = AtomicBool::new(false);
+        let mut calls = 0;
+        let runs = run_watch_loop(&rx, Duration::from_millis(20), &stop, || calls += 1);
+
+        assert_eq!(runs, 1);
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn separated_changes_each_trigger_a_reprocess() {
+        let (tx, rx) = mpsc::channel();
+        let sender = thread::spawn(move || {
+            tx.send(()).unwrap();
+            thread::sleep(Duration::from_millis(200));
+            tx.send(()).unwrap();
+        });
+
+        let stop = AtomicBool::new(false);
+        let runs = run_watch_loop(&rx, Duration::from_millis(20), &stop, || {});
+        sender.join().unwrap();
+
+        assert_eq!(runs, 2);
+    }
+
+    #[test]
+    fn stop_flag_ends_loop_without_reprocessing() {
+        let (_tx, rx) = mpsc::channel::<()>();
+        let stop = AtomicBool::new(true);
+
+        let runs = run_watch_loop(&rx, Duration::from_millis(20), &stop, || {
+            panic!("should not reprocess after stop")
+        });
+
+        assert_eq!(runs, 0);
+    }
+}
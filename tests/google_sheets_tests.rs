@@ -227,3 +227,87 @@ fn test_concurrent_url_conversion() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+/// Test worksheet-specific export URLs used when fetching individual tabs
+#[test]
+fn test_google_sheets_csv_url_for_gid() -> Result<(), Box<dyn std::error::Error>> {
+    let csv_url = CsvModifier::google_sheets_to_csv_url_for_gid(
+        "https://docs.google.com/spreadsheets/d/test123/edit#gid=0",
+        "1843120417",
+    )?;
+    assert_eq!(
+        csv_url,
+        "https://docs.google.com/spreadsheets/d/test123/export?format=csv&gid=1843120417"
+    );
+
+    assert!(CsvModifier::google_sheets_to_csv_url_for_gid(
+        "https://docs.google.com/spreadsheets/d/test123/edit",
+        "abc"
+    )
+    .is_err());
+
+    Ok(())
+}
+
+/// Test that worksheet tabs are concatenated under a single header row
+#[test]
+fn test_concatenate_sheet_tabs() -> Result<(), Box<dyn std::error::Error>> {
+    let tabs = vec![
+        (
+            "0".to_string(),
+            "accessIdentifier,title\n2024_19_01_001,Box 1 Item\n".to_string(),
+        ),
+        (
+            "77".to_string(),
+            "accessIdentifier,title\n2024_19_02_001,\"Box 2, Item\"\n".to_string(),
+        ),
+    ];
+
+    let combined = CsvModifier::concatenate_sheet_tabs(&tabs)?;
+    assert_eq!(
+        combined,
+        "accessIdentifier,title\n2024_19_01_001,Box 1 Item\n2024_19_02_001,\"Box 2, Item\"\n"
+    );
+
+    Ok(())
+}
+
+/// Test that tabs with different headers are rejected
+#[test]
+fn test_concatenate_sheet_tabs_rejects_mismatched_headers() {
+    let tabs = vec![
+        (
+            "0".to_string(),
+            "accessIdentifier,title\n2024_19_01_001,Box 1 Item\n".to_string(),
+        ),
+        (
+            "77".to_string(),
+            "accessIdentifier,fileTitle\n2024_19_02_001,Box 2 Item\n".to_string(),
+        ),
+    ];
+
+    let err = CsvModifier::concatenate_sheet_tabs(&tabs).unwrap_err();
+    assert!(err.to_string().contains("gid=77"), "{}", err);
+}
+
+/// Test that --print-url-only prints the export URL for the chosen tab without fetching
+#[test]
+fn test_print_url_only_with_gid() -> Result<(), Box<dyn std::error::Error>> {
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_organise"))
+        .args([
+            "--url",
+            "https://docs.google.com/spreadsheets/d/1BxiMVs0XRA5nFMdKvBdBZjgmUUqptlbs74OgvE2upms/edit#gid=0",
+            "--gid",
+            "123456",
+            "--print-url-only",
+        ])
+        .output()?;
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        "https://docs.google.com/spreadsheets/d/1BxiMVs0XRA5nFMdKvBdBZjgmUUqptlbs74OgvE2upms/export?format=csv&gid=123456\n"
+    );
+
+    Ok(())
+}
@@ -3,7 +3,13 @@
 //! These tests exercise the public API of the library and test the interaction
 //! between multiple components, simulating real-world usage scenarios.
 
-use organise::{ColumnModifier, CsvModifier, FileExtensionModifier, ParentIdModifier, RowContext};
+use organise::{
+    process_csv_and_maybe_generate_items, ColumnModifier, CsvDialect, CsvModifier, ExecModifier,
+    FileExtensionModifier,
+    ItemGenerationOptions, Modifier, OutputFormat, ParentIdModifier, ProcessingOptions, RowContext,
+    TitleTidyModifier,
+    UrlValidator,
+};
 use std::fs::File;
 use std::io::{Cursor, Write};
 use tempfile::tempdir;
@@ -22,6 +28,7 @@ fn create_temp_csv(
 
 /// Test basic CSV processing with built-in modifiers
 #[test]
+#[allow(clippy::needless_borrow)]
 fn test_basic_csv_processing() -> Result<(), Box<dyn std::error::Error>> {
     let csv_content = r#"accessIdentifier,file,file_extension,parent_id,title
 2024_19_01_001,document,pdf,,First Document
@@ -40,6 +47,10 @@ fn test_basic_csv_processing() -> Result<(), Box<dyn std::error::Error>> {
     // Verify statistics
     assert_eq!(stats.total_rows, 3);
     assert_eq!(stats.cells_modified, 9); // 3 rows × (parent_id + file + field_identifier)
+    assert_eq!(
+        (stats.cells_sanitized, stats.cells_modified_by_modifiers, stats.cells_subdelimiter_replaced),
+        (0, 9, 0)
+    );
     assert_eq!(stats.validation_failures, 0);
     assert_eq!(stats.columns_processed.len(), 4);
     assert!(stats.columns_processed.contains("parent_id"));
@@ -74,6 +85,7 @@ fn test_basic_csv_processing() -> Result<(), Box<dyn std::error::Error>> {
 
 /// Google Sheets exports often omit derived columns such as `parent_id`; they must still be created.
 #[test]
+#[allow(clippy::needless_borrow)]
 fn test_csv_without_parent_id_column_gets_derived_parent_id() -> Result<(), Box<dyn std::error::Error>>
 {
     let csv_content = r#"accessIdentifier,file,file_extension,fileTitle
@@ -103,6 +115,7 @@ fn test_csv_without_parent_id_column_gets_derived_parent_id() -> Result<(), Box<
 
 /// Verify that the file extension modifier accepts the common misspelling `file_extention`
 #[test]
+#[allow(clippy::needless_borrow)]
 fn test_csv_processing_with_file_extention_alias() -> Result<(), Box<dyn std::error::Error>> {
     let csv_content = r#"accessIdentifier,file,file_extention,parent_id,title
 2024_19_01_001,document,pdf,,First Document
@@ -120,6 +133,10 @@ fn test_csv_processing_with_file_extention_alias() -> Result<(), Box<dyn std::er
     assert_eq!(stats.total_rows, 2);
     assert_eq!(stats.validation_failures, 0);
     assert_eq!(stats.cells_modified, 6);
+    assert_eq!(
+        (stats.cells_sanitized, stats.cells_modified_by_modifiers, stats.cells_subdelimiter_replaced),
+        (0, 6, 0)
+    );
 
     let output_content = std::fs::read_to_string(&output_path)?;
     let mut lines = output_content.lines();
@@ -140,6 +157,7 @@ fn test_csv_processing_with_file_extention_alias() -> Result<(), Box<dyn std::er
 
 /// Ensure placeholder values like `#VALUE!` are treated as empty during processing
 #[test]
+#[allow(clippy::needless_borrow)]
 fn test_csv_processing_ignores_value_placeholders() -> Result<(), Box<dyn std::error::Error>> {
     let csv_content = r#"accessIdentifier,file,file_extension,parent_id,title
 #VALUE!,#VALUE!,pdf,#VALUE!,Broken Row
@@ -200,6 +218,10 @@ fn test_csv_processing_with_validation_failures() -> Result<(), Box<dyn std::err
     assert_eq!(stats.skipped_rows, 2);
     assert!(stats.validation_failures > 0);
     assert_eq!(stats.cells_modified, 5); // Row 1: 3 mods, Row 3: 2 mods (parent_id + field_identifier)
+    assert_eq!(
+        (stats.cells_sanitized, stats.cells_modified_by_modifiers, stats.cells_subdelimiter_replaced),
+        (0, 5, 0)
+    );
 
     // Verify that the valid row was processed correctly
     let output_content = std::fs::read_to_string(&output_path)?;
@@ -245,6 +267,10 @@ foo bar,another description,category2
     assert_eq!(stats.total_rows, 2);
     assert_eq!(stats.skipped_rows, 1);
     assert_eq!(stats.cells_modified, 2); // Two valid titles
+    assert_eq!(
+        (stats.cells_sanitized, stats.cells_modified_by_modifiers, stats.cells_subdelimiter_replaced),
+        (0, 2, 0)
+    );
     assert_eq!(stats.validation_failures, 1); // One empty title
 
     let output_content = std::fs::read_to_string(&output_path)?;
@@ -274,6 +300,10 @@ fn test_rows_with_zero_suffix_access_identifier_are_skipped(
     assert_eq!(stats.skipped_rows, 1);
     assert_eq!(stats.total_rows, 1);
     assert_eq!(stats.cells_modified, 3);
+    assert_eq!(
+        (stats.cells_sanitized, stats.cells_modified_by_modifiers, stats.cells_subdelimiter_replaced),
+        (0, 3, 0)
+    );
     assert_eq!(stats.validation_failures, 1);
 
     let output_content = std::fs::read_to_string(&output_path)?;
@@ -305,6 +335,10 @@ fn test_duplicate_access_identifiers_are_skipped() -> Result<(), Box<dyn std::er
     assert_eq!(stats.skipped_rows, 1);
     assert_eq!(stats.validation_failures, 1);
     assert_eq!(stats.cells_modified, 6);
+    assert_eq!(
+        (stats.cells_sanitized, stats.cells_modified_by_modifiers, stats.cells_subdelimiter_replaced),
+        (0, 6, 0)
+    );
 
     let output_content = std::fs::read_to_string(&output_path)?;
     assert!(output_content.contains("2024_19_01/document.pdf"));
@@ -314,6 +348,361 @@ fn test_duplicate_access_identifiers_are_skipped() -> Result<(), Box<dyn std::er
     Ok(())
 }
 
+/// The first occurrence of a duplicated accessIdentifier is recorded as kept
+#[test]
+fn test_dedup_kept_output_records_first_row() -> Result<(), Box<dyn std::error::Error>> {
+    let csv_content = r#"accessIdentifier,file,file_extension,parent_id,title
+2024_19_01_001,document,pdf,,Original Row
+2024_19_01_002,report,pdf,,Unique Row
+2024_19_01_001,image,jpg,,Duplicate Row
+2024_19_01_001,scan,tif,,Second Duplicate"#;
+
+    let (input_path, temp_dir) = create_temp_csv(csv_content)?;
+    let output_path = format!("{}_output.csv", input_path);
+    let kept_path = temp_dir.path().join("kept.csv");
+
+    let modifier = CsvModifier::new()
+        .add_column_modifier("parent_id", ParentIdModifier)
+        .with_dedup_kept_output(kept_path.to_str().unwrap());
+
+    let stats = modifier.process_file(&input_path, &output_path)?;
+    assert_eq!(stats.total_rows, 2);
+    assert_eq!(stats.skipped_rows, 2);
+
+    let kept_content = std::fs::read_to_string(&kept_path)?;
+    assert_eq!(kept_content, "accessIdentifier,kept_row_number\n2024_19_01_001,1\n");
+
+    Ok(())
+}
+
+/// A tiny dedup memory cap forces the on-disk spill path without changing the result
+#[test]
+fn test_dedup_memory_cap_spills_and_stays_exact() -> Result<(), Box<dyn std::error::Error>> {
+    let csv_content = r#"accessIdentifier,title
+2024_19_01_001,First
+2024_19_01_002,Second
+2024_19_01_003,Third
+2024_19_01_001,Duplicate of first
+2024_19_01_004,Fourth
+2024_19_01_003,Duplicate of third
+2024_19_01_005,Fifth
+2024_19_01_004,Duplicate of fourth"#;
+
+    let (input_path, temp_dir) = create_temp_csv(csv_content)?;
+    let uncapped_path = temp_dir.path().join("uncapped.csv");
+    let capped_path = temp_dir.path().join("capped.csv");
+    let kept_path = temp_dir.path().join("kept.csv");
+
+    let uncapped = CsvModifier::new().process_file(&input_path, uncapped_path.to_str().unwrap())?;
+    let capped = CsvModifier::new()
+        .with_dedup_memory_cap(1)
+        .with_dedup_kept_output(kept_path.to_str().unwrap())
+        .process_file(&input_path, capped_path.to_str().unwrap())?;
+
+    assert_eq!(capped.total_rows, 5);
+    assert_eq!(capped.skipped_rows, 3);
+    assert_eq!(capped.total_rows, uncapped.total_rows);
+    assert_eq!(
+        std::fs::read_to_string(&capped_path)?,
+        std::fs::read_to_string(&uncapped_path)?
+    );
+    assert_eq!(
+        std::fs::read_to_string(&kept_path)?,
+        "accessIdentifier,kept_row_number\n2024_19_01_001,1\n2024_19_01_003,3\n2024_19_01_004,5\n"
+    );
+
+    Ok(())
+}
+
+/// file_original keeps the input file value next to the composed path
+#[test]
+fn test_keep_original_file_column() -> Result<(), Box<dyn std::error::Error>> {
+    let csv_content = r#"accessIdentifier,file,file_extension,parent_id,title
+2024_19_01_001,document,pdf,,Annual Report"#;
+
+    let (input_path, _temp_dir) = create_temp_csv(csv_content)?;
+    let output_path = format!("{}_output.csv", input_path);
+
+    CsvModifier::new()
+        .add_column_modifier("parent_id", ParentIdModifier)
+        .add_column_modifier("file", FileExtensionModifier)
+        .with_original_file_column()
+        .process_file(&input_path, &output_path)?;
+
+    let mut reader = csv::Reader::from_path(&output_path)?;
+    let headers = reader.headers()?.clone();
+    assert_eq!(headers.iter().next_back(), Some("file_original"));
+    let file_idx = headers.iter().position(|h| h == "file").unwrap();
+    let original_idx = headers.iter().position(|h| h == "file_original").unwrap();
+
+    let record = reader.records().next().unwrap()?;
+    assert_eq!(record.get(file_idx), Some("2024_19_01/document.pdf"));
+    assert_eq!(record.get(original_idx), Some("document"));
+
+    Ok(())
+}
+
+/// Protected columns skip sanitization, `;` replacement and modifiers
+#[test]
+fn test_protected_columns_pass_through_byte_for_byte() -> Result<(), Box<dyn std::error::Error>> {
+    let protected_value = "Lal;\u{a0}Singh ; Kaur";
+    let csv_content = format!(
+        "accessIdentifier,file,file_extension,subject,title\n\
+         2024_19_01_001,document,pdf,{0},{0}",
+        protected_value
+    );
+
+    let (input_path, _temp_dir) = create_temp_csv(&csv_content)?;
+    let output_path = format!("{}_output.csv", input_path);
+
+    CsvModifier::new()
+        .add_column_modifier("file", FileExtensionModifier)
+        .with_protected_columns(["subject", "file"])
+        .process_file(&input_path, &output_path)?;
+
+    let output = std::fs::read_to_string(&output_path)?;
+    assert!(output.contains(&format!(",document,pdf,{},", protected_value)));
+
+    let mut reader = csv::Reader::from_path(&output_path)?;
+    let record = reader.records().next().unwrap()?;
+    assert_eq!(record.get(1), Some("document"));
+    assert_eq!(record.get(3), Some(protected_value));
+    assert_eq!(record.get(4), Some("Lal| Singh | Kaur"));
+
+    Ok(())
+}
+
+/// Passthrough verification expects protected columns to match the raw input
+#[test]
+fn test_verify_passthrough_with_protected_columns() -> Result<(), Box<dyn std::error::Error>> {
+    let csv_content = "accessIdentifier,notes,title\n2024_19_01_001,a;b\u{a0}c,Annual Report";
+
+    let (input_path, _temp_dir) = create_temp_csv(csv_content)?;
+    let output_path = format!("{}_output.csv", input_path);
+
+    CsvModifier::new()
+        .with_protected_columns(["notes"])
+        .with_passthrough_verification()
+        .process_file(&input_path, &output_path)?;
+
+    let output = std::fs::read_to_string(&output_path)?;
+    assert!(output.contains(",a;b\u{a0}c,"), "{}", output);
+
+    Ok(())
+}
+
+/// Title tidying is opt-in and its edits count as modified cells
+#[test]
+fn test_tidy_titles_counts_changes() -> Result<(), Box<dyn std::error::Error>> {
+    let csv_content = r#"accessIdentifier,title
+2024_19_01_001,Annual Report.
+2024_19_01_002,Vol. 2"#;
+
+    let (input_path, temp_dir) = create_temp_csv(csv_content)?;
+    let plain_path = temp_dir.path().join("plain.csv");
+    let tidy_path = temp_dir.path().join("tidy.csv");
+
+    let plain = CsvModifier::new().process_file(&input_path, plain_path.to_str().unwrap())?;
+    let tidy = CsvModifier::new()
+        .add_column_modifier("title", TitleTidyModifier)
+        .process_file(&input_path, tidy_path.to_str().unwrap())?;
+
+    assert_eq!(tidy.cells_modified, plain.cells_modified + 1);
+    assert_eq!(tidy.cells_modified_by_modifiers, plain.cells_modified_by_modifiers + 1);
+
+    let plain_output = std::fs::read_to_string(&plain_path)?;
+    assert!(plain_output.contains("2024_19_01_001,Annual Report.,"), "{}", plain_output);
+    let tidy_output = std::fs::read_to_string(&tidy_path)?;
+    assert!(tidy_output.contains("2024_19_01_001,Annual Report,"), "{}", tidy_output);
+    assert!(tidy_output.contains("2024_19_01_002,Vol. 2,"), "{}", tidy_output);
+
+    Ok(())
+}
+
+/// An external command's stdout replaces the cell; the row is available as JSON
+#[cfg(unix)]
+#[test]
+fn test_exec_modifier_transforms_cells() -> Result<(), Box<dyn std::error::Error>> {
+    let csv_content = r#"accessIdentifier,title,field_note
+2024_19_01_001,annual report,
+2024_19_01_002,minutes,"#;
+
+    let (input_path, _temp_dir) = create_temp_csv(csv_content)?;
+    let output_path = format!("{}_output.csv", input_path);
+    let timeout = std::time::Duration::from_secs(10);
+
+    let stats = CsvModifier::new()
+        .add_column_modifier("title", ExecModifier::new("tr a-z A-Z", timeout))
+        .add_column_modifier(
+            "field_note",
+            ExecModifier::new(r#"printf '%s' "$ORGANISE_ROW" | grep -c '"accessIdentifier":"2024_19_01_00'"#, timeout),
+        )
+        .process_file(&input_path, &output_path)?;
+    assert_eq!(stats.validation_failures, 0);
+
+    let output = std::fs::read_to_string(&output_path)?;
+    assert!(output.contains("2024_19_01_001,ANNUAL REPORT,1,"), "{}", output);
+    assert!(output.contains("2024_19_01_002,MINUTES,1,"), "{}", output);
+
+    Ok(())
+}
+
+/// Non-zero exits and timeouts are validation failures that leave the cell unchanged
+#[cfg(unix)]
+#[test]
+fn test_exec_modifier_failures_are_validation_failures() -> Result<(), Box<dyn std::error::Error>> {
+    let csv_content = r#"accessIdentifier,title,field_note
+2024_19_01_001,annual report,draft"#;
+
+    let (input_path, _temp_dir) = create_temp_csv(csv_content)?;
+    let output_path = format!("{}_output.csv", input_path);
+
+    let stats = CsvModifier::new()
+        .add_column_modifier(
+            "title",
+            ExecModifier::new("echo replaced; exit 3", std::time::Duration::from_secs(10)),
+        )
+        .add_column_modifier(
+            "field_note",
+            ExecModifier::new("sleep 5", std::time::Duration::from_millis(200)),
+        )
+        .process_file(&input_path, &output_path)?;
+
+    assert_eq!(stats.validation_failures, 2);
+    assert_eq!(stats.total_rows, 1);
+    let output = std::fs::read_to_string(&output_path)?;
+    assert!(output.contains("2024_19_01_001,annual report,draft,"), "{}", output);
+
+    Ok(())
+}
+
+/// A timeout kills the processes the shell started too, so their pipes close and the run moves on
+#[cfg(unix)]
+#[test]
+fn test_exec_modifier_timeout_kills_child_processes() -> Result<(), Box<dyn std::error::Error>> {
+    let csv_content = r#"accessIdentifier,title
+2024_19_01_001,annual report"#;
+
+    let (input_path, _temp_dir) = create_temp_csv(csv_content)?;
+    let output_path = format!("{}_output.csv", input_path);
+
+    let started = std::time::Instant::now();
+    let stats = CsvModifier::new()
+        .add_column_modifier(
+            "title",
+            ExecModifier::new("sleep 30; echo late", std::time::Duration::from_millis(200)),
+        )
+        .process_file(&input_path, &output_path)?;
+
+    assert!(started.elapsed() < std::time::Duration::from_secs(10), "{:?}", started.elapsed());
+    assert_eq!(stats.validation_failures, 1);
+    let output = std::fs::read_to_string(&output_path)?;
+    assert!(output.contains("2024_19_01_001,annual report"), "{}", output);
+
+    Ok(())
+}
+
+/// Exec modifiers do not run on protected columns
+#[cfg(unix)]
+#[test]
+fn test_exec_modifier_skips_protected_columns() -> Result<(), Box<dyn std::error::Error>> {
+    let csv_content = r#"accessIdentifier,title,field_note
+2024_19_01_001,annual report,draft"#;
+
+    let (input_path, temp_dir) = create_temp_csv(csv_content)?;
+    let output_path = format!("{}_output.csv", input_path);
+    let marker = temp_dir.path().join("ran");
+    let command = format!("touch '{}'; tr a-z A-Z", marker.display());
+
+    let stats = CsvModifier::new()
+        .add_column_modifier("title", ExecModifier::new("tr a-z A-Z", std::time::Duration::from_secs(10)))
+        .add_column_modifier("field_note", ExecModifier::new(&command, std::time::Duration::from_secs(10)))
+        .with_protected_columns(["field_note"])
+        .process_file(&input_path, &output_path)?;
+
+    assert!(!marker.exists(), "exec modifier ran on a protected column");
+    assert_eq!(stats.validation_failures, 0);
+    let output = std::fs::read_to_string(&output_path)?;
+    assert!(output.contains("2024_19_01_001,ANNUAL REPORT,draft"), "{}", output);
+
+    Ok(())
+}
+
+/// Exec modifiers chain after title tidying and after each other on the same column
+#[cfg(unix)]
+#[test]
+fn test_exec_modifier_chains_with_other_modifiers() -> Result<(), Box<dyn std::error::Error>> {
+    let csv_content = r#"accessIdentifier,title
+2024_19_01_001,report."#;
+
+    let (input_path, temp_dir) = create_temp_csv(csv_content)?;
+    let output_path = temp_dir.path().join("processed.csv");
+
+    let processing_options = ProcessingOptions {
+        tidy_titles: vec!["title".to_string()],
+        exec_modifiers: vec![
+            ("title".to_string(), "tr a-z A-Z".to_string()),
+            ("title".to_string(), "sed 's/$/!/'".to_string()),
+        ],
+        allow_exec: true,
+        ..Default::default()
+    };
+    let result = process_csv_and_maybe_generate_items(
+        &input_path,
+        output_path.to_str(),
+        None,
+        &[Modifier::ParentId],
+        &[],
+        None,
+        &processing_options,
+        false,
+        None,
+        None,
+        &ItemGenerationOptions::default(),
+    )?;
+
+    assert_eq!(result.processing_stats.validation_failures, 0);
+    let output = std::fs::read_to_string(&output_path)?;
+    assert!(output.contains("2024_19_01_001,REPORT!,"), "{}", output);
+
+    Ok(())
+}
+
+/// URL validation runs after, not instead of, a built-in modifier on the same column
+#[test]
+fn test_validate_urls_composes_with_default_modifier() -> Result<(), Box<dyn std::error::Error>> {
+    let csv_content = r#"accessIdentifier,file,file_extension,title
+2024_19_01_001,document,pdf,Annual Report"#;
+
+    let (input_path, temp_dir) = create_temp_csv(csv_content)?;
+    let output_path = temp_dir.path().join("processed.csv");
+
+    let processing_options = ProcessingOptions {
+        validate_urls: vec!["file".to_string()],
+        ..Default::default()
+    };
+    let result = process_csv_and_maybe_generate_items(
+        &input_path,
+        output_path.to_str(),
+        None,
+        &[Modifier::FileExtension],
+        &[],
+        None,
+        &processing_options,
+        false,
+        None,
+        None,
+        &ItemGenerationOptions::default(),
+    )?;
+
+    // The composed path is still written, and it is not a URL
+    assert_eq!(result.processing_stats.validation_failures, 1);
+    let processed = std::fs::read_to_string(&output_path)?;
+    assert!(processed.contains("2024_19_01/document.pdf"));
+
+    Ok(())
+}
+
 /// Ensure rows with empty titles are skipped before modifiers run
 #[test]
 fn test_rows_with_empty_title_are_skipped() -> Result<(), Box<dyn std::error::Error>> {
@@ -344,6 +733,7 @@ fn test_rows_with_empty_title_are_skipped() -> Result<(), Box<dyn std::error::Er
 
 /// Ensure textual data is sanitized and field_description is quoted
 #[test]
+#[allow(clippy::format_in_format_args)]
 fn test_text_sanitization_and_description_quotes() -> Result<(), Box<dyn std::error::Error>> {
     let nbsp = '\u{00A0}';
     let csv_content = format!(
@@ -386,6 +776,388 @@ fn test_text_sanitization_and_description_quotes() -> Result<(), Box<dyn std::er
     Ok(())
 }
 
+/// URL columns flag invalid values and optionally repair schemeless ones
+#[test]
+fn test_url_validation_flags_invalid_and_fixes_missing_scheme(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let csv_content = r#"accessIdentifier,title,field_rights_url
+2024_19_01_001,Valid,https://rightsstatements.org/vocab/InC/1.0/
+2024_19_01_002,Invalid,http://exa mple.com/rights
+2024_19_01_003,Schemeless,rightsstatements.org/vocab/NoC-US/1.0/"#;
+
+    let (input_path, _temp_dir) = create_temp_csv(csv_content)?;
+
+    let strict_output = format!("{}_strict.csv", input_path);
+    let stats = CsvModifier::new()
+        .add_column_modifier("field_rights_url", UrlValidator::new(false))
+        .process_file(&input_path, &strict_output)?;
+
+    // Invalid URLs are flagged but the rows are still written
+    assert_eq!(stats.total_rows, 3);
+    assert_eq!(stats.validation_failures, 2);
+
+    let fixing_output = format!("{}_fixed.csv", input_path);
+    let stats = CsvModifier::new()
+        .add_column_modifier("field_rights_url", UrlValidator::new(true))
+        .process_file(&input_path, &fixing_output)?;
+
+    assert_eq!(stats.total_rows, 3);
+    assert_eq!(stats.validation_failures, 1);
+    assert_eq!(stats.cells_modified, 4); // 3 field_identifier copies + 1 scheme fix
+    assert_eq!(
+        (stats.cells_sanitized, stats.cells_modified_by_modifiers, stats.cells_subdelimiter_replaced),
+        (0, 4, 0)
+    );
+
+    let output_content = std::fs::read_to_string(&fixing_output)?;
+    assert!(output_content.contains(",https://rightsstatements.org/vocab/InC/1.0/,"));
+    assert!(output_content.contains(",http://exa mple.com/rights,"));
+    assert!(output_content.contains(",https://rightsstatements.org/vocab/NoC-US/1.0/,"));
+
+    Ok(())
+}
+
+/// Excel-friendly output starts with a UTF-8 BOM and terminates records with CRLF
+#[test]
+fn test_excel_friendly_output_has_bom_and_crlf() -> Result<(), Box<dyn std::error::Error>> {
+    let csv_content = r#"accessIdentifier,file,file_extension,parent_id,fileTitle
+2024_19_01_001,document,pdf,,Montréal Stories
+2024_19_01_002,image,jpg,,Second Image"#;
+
+    let (input_path, _temp_dir) = create_temp_csv(csv_content)?;
+    let output_path = format!("{}_output.csv", input_path);
+
+    let modifier = CsvModifier::new()
+        .add_column_modifier("parent_id", ParentIdModifier)
+        .with_excel_friendly_output();
+
+    let stats = modifier.process_file(&input_path, &output_path)?;
+    assert_eq!(stats.total_rows, 2);
+
+    let output_bytes = std::fs::read(&output_path)?;
+    assert!(output_bytes.starts_with(b"\xEF\xBB\xBF"));
+
+    let output_content = String::from_utf8(output_bytes[3..].to_vec())?;
+    assert_eq!(
+        output_content,
+        "accessIdentifier,file,file_extension,parent_id,fileTitle,field_identifier\r\n\
+         2024_19_01_001,document,pdf,2024_19_01,Montréal Stories,2024_19_01_001\r\n\
+         2024_19_01_002,image,jpg,2024_19_01,Second Image,2024_19_01_002\r\n"
+    );
+
+    // The BOM must not leak into the first header when the output is read back
+    let items_path = format!("{}_items.csv", input_path);
+    let items_stats = organise::ItemCsvGenerator::generate(&output_path, &items_path, None)?;
+    assert_eq!(items_stats.unique_parents, 1);
+
+    Ok(())
+}
+
+/// Canonical output is sorted by accessIdentifier, fully quoted and LF-terminated
+#[test]
+fn test_canonical_output_is_sorted_and_quoted() -> Result<(), Box<dyn std::error::Error>> {
+    let csv_content = "accessIdentifier,title,notes\r\n\
+                       2024_19_01_003,Third,\r\n\
+                       2024_19_01_001,First,\"has, comma\"\r\n\
+                       2024_19_01_002,Second,plain\r\n";
+
+    let (input_path, _temp_dir) = create_temp_csv(csv_content)?;
+    let output_path = format!("{}_output.csv", input_path);
+
+    let stats = CsvModifier::new()
+        .with_canonical_output()
+        .process_file(&input_path, &output_path)?;
+    assert_eq!(stats.total_rows, 3);
+
+    let output_content = std::fs::read_to_string(&output_path)?;
+    assert_eq!(
+        output_content,
+        "\"accessIdentifier\",\"title\",\"notes\",\"field_identifier\"\n\
+         \"2024_19_01_001\",\"First\",\"has, comma\",\"2024_19_01_001\"\n\
+         \"2024_19_01_002\",\"Second\",\"plain\",\"2024_19_01_002\"\n\
+         \"2024_19_01_003\",\"Third\",\"\",\"2024_19_01_003\"\n"
+    );
+
+    Ok(())
+}
+
+/// Passthrough verification accepts sanitization, `;` replacement, skipped rows and canonical sorting
+#[test]
+fn test_verify_passthrough_accepts_expected_changes() -> Result<(), Box<dyn std::error::Error>> {
+    let csv_content = "accessIdentifier,title,description,subjects,parent_id\n\
+                       2024_19_01_002,Second\u{a0}Item,\"a; b\",Maps;Roads,\n\
+                       2024_19_01_000,Container,,,\n\
+                       2024_19_01_001,First,,Letters,\n\
+                       2024_19_01_001,Duplicate,,,\n";
+
+    let (input_path, _temp_dir) = create_temp_csv(csv_content)?;
+    let output_path = format!("{}_output.csv", input_path);
+
+    let stats = CsvModifier::new()
+        .add_column_modifier("parent_id", ParentIdModifier)
+        .with_canonical_output()
+        .with_passthrough_verification()
+        .process_file(&input_path, &output_path)?;
+    assert_eq!(stats.total_rows, 2);
+
+    let output_content = std::fs::read_to_string(&output_path)?;
+    assert!(output_content.contains("\"Second Item\",\"a; b\",\"Maps|Roads\",\"2024_19_01\""));
+
+    Ok(())
+}
+
+/// `cells_modified` is broken down into sanitization, modifier and subdelimiter counts
+#[test]
+fn test_cells_modified_breakdown() -> Result<(), Box<dyn std::error::Error>> {
+    let csv_content = "accessIdentifier,title,subjects,description\n\
+                       2024_19_01_001,First\u{a0}Item,Maps;Roads,\"a; b\"\n\
+                       2024_19_01_002,Second,Letters,\n";
+
+    let (input_path, _temp_dir) = create_temp_csv(csv_content)?;
+    let output_path = format!("{}_output.csv", input_path);
+
+    let stats = CsvModifier::new().process_file(&input_path, &output_path)?;
+
+    assert_eq!(stats.cells_sanitized, 1); // NBSP in the first title
+    assert_eq!(stats.cells_modified_by_modifiers, 2); // field_identifier copies
+    assert_eq!(stats.cells_subdelimiter_replaced, 1); // subjects, not description
+    assert_eq!(stats.cells_modified, 4);
+
+    Ok(())
+}
+
+/// JSON lines output writes one string-valued object per kept row, keyed by header name
+#[test]
+fn test_jsonl_output_serializes_processed_rows() -> Result<(), Box<dyn std::error::Error>> {
+    let csv_content = "accessIdentifier,title,subjects,count\n\
+                       2024_19_01_001,\"Letter, \"\"draft\"\"\",Maps;Roads,007\n\
+                       2024_19_01_000,Container,,\n";
+
+    let (input_path, _temp_dir) = create_temp_csv(csv_content)?;
+    let output_path = format!("{}_output.jsonl", input_path);
+
+    let stats = CsvModifier::new()
+        .add_column_modifier("parent_id", ParentIdModifier)
+        .with_output_format(OutputFormat::Jsonl)
+        .process_file(&input_path, &output_path)?;
+    assert_eq!(stats.total_rows, 1);
+    assert_eq!(stats.skipped_rows, 1);
+
+    let output_content = std::fs::read_to_string(&output_path)?;
+    assert_eq!(
+        output_content,
+        "{\"accessIdentifier\":\"2024_19_01_001\",\"title\":\"Letter, \\\"draft\\\"\",\
+         \"subjects\":\"Maps|Roads\",\"count\":\"007\",\
+         \"field_identifier\":\"2024_19_01_001\",\"parent_id\":\"2024_19_01\"}\n"
+    );
+
+    Ok(())
+}
+
+/// A `file` suffix that disagrees with `file_extension` is flagged, and fails the run when strict
+#[test]
+fn test_file_extension_mismatch_is_flagged() -> Result<(), Box<dyn std::error::Error>> {
+    let csv_content = "accessIdentifier,title,file,file_extension\n\
+                       2024_19_01_001,Scan,doc.pdf,jpg\n\
+                       2024_19_01_002,Photo,photo.JPG,jpg\n\
+                       2024_19_01_003,Notes,notes,txt\n";
+
+    let (input_path, _temp_dir) = create_temp_csv(csv_content)?;
+    let output_path = format!("{}_output.csv", input_path);
+
+    let stats = CsvModifier::new()
+        .add_column_modifier("file", FileExtensionModifier)
+        .process_file(&input_path, &output_path)?;
+    assert_eq!(stats.total_rows, 3);
+    assert_eq!(stats.extension_mismatches, 1);
+
+    let err = CsvModifier::new()
+        .add_column_modifier("file", FileExtensionModifier)
+        .with_strict_extension_check()
+        .process_file(&input_path, &output_path)
+        .unwrap_err();
+    assert!(err.to_string().contains("1 row(s)"), "{}", err);
+
+    Ok(())
+}
+
+/// A failed strict check leaves no processed, crosswalk or dedup-kept output behind
+#[test]
+fn test_strict_failure_removes_written_outputs() -> Result<(), Box<dyn std::error::Error>> {
+    let csv_content = "accessIdentifier,title,file,file_extension\n\
+                       2024_19_01_001,Scan,doc.pdf,jpg\n\
+                       2024_19_01_001,Scan again,doc.pdf,jpg\n";
+
+    let (input_path, temp_dir) = create_temp_csv(csv_content)?;
+    let output_path = temp_dir.path().join("processed.csv");
+    let crosswalk_path = temp_dir.path().join("crosswalk.csv");
+    let kept_path = temp_dir.path().join("dedup-kept.csv");
+
+    let result = CsvModifier::new()
+        .add_column_modifier("file", FileExtensionModifier)
+        .with_crosswalk_output(crosswalk_path.to_str().unwrap())
+        .with_dedup_kept_output(kept_path.to_str().unwrap())
+        .with_strict_extension_check()
+        .process_file(&input_path, output_path.to_str().unwrap());
+
+    assert!(result.is_err());
+    assert!(!output_path.exists());
+    assert!(!crosswalk_path.exists());
+    assert!(!kept_path.exists());
+
+    Ok(())
+}
+
+/// The crosswalk pairs every written row's derived parent_id and file with its source row
+#[test]
+fn test_crosswalk_matches_processed_rows() -> Result<(), Box<dyn std::error::Error>> {
+    let csv_content = "accessIdentifier,title,file,file_extension\n\
+                       2024_19_01_001,Letter,scan1.tif,jpg\n\
+                       2024_19_01_001,Duplicate,scan2.tif,jpg\n\
+                       2024_19_02_003,Photo,photo,png\n";
+
+    let (input_path, temp_dir) = create_temp_csv(csv_content)?;
+    let output_path = format!("{}_output.csv", input_path);
+    let crosswalk_path = temp_dir.path().join("crosswalk.csv");
+
+    let stats = CsvModifier::new()
+        .add_column_modifier("parent_id", ParentIdModifier)
+        .add_column_modifier("file", FileExtensionModifier)
+        .with_crosswalk_output(crosswalk_path.to_str().unwrap())
+        .process_file(&input_path, &output_path)?;
+    assert_eq!(stats.total_rows, 2);
+
+    let crosswalk = std::fs::read_to_string(&crosswalk_path)?;
+    assert_eq!(
+        crosswalk,
+        "source_row,original_accessIdentifier,parent_id,file\n\
+         1,2024_19_01_001,2024_19_01,2024_19_01/scan1.jpg\n\
+         3,2024_19_02_003,2024_19_02,2024_19_02/photo.png\n"
+    );
+
+    let mut output = csv::Reader::from_path(&output_path)?;
+    let headers = output.headers()?.clone();
+    let parent_idx = headers.iter().position(|h| h == "parent_id").unwrap();
+    let file_idx = headers.iter().position(|h| h == "file").unwrap();
+    let mut crosswalk_reader = csv::Reader::from_path(&crosswalk_path)?;
+    for (processed, mapped) in output.records().zip(crosswalk_reader.records()) {
+        let (processed, mapped) = (processed?, mapped?);
+        assert_eq!(processed.get(parent_idx), mapped.get(2));
+        assert_eq!(processed.get(file_idx), mapped.get(3));
+    }
+
+    Ok(())
+}
+
+/// Single-quoted input with backslash escapes is read correctly and written as standard CSV
+#[test]
+fn test_single_quote_dialect_input_writes_standard_csv() -> Result<(), Box<dyn std::error::Error>> {
+    let csv_content = "accessIdentifier,title,field_description\n\
+                       2024_19_01_001,'Smith, Jane','She said \\'hello\\' twice'\n";
+    let (input_path, temp_dir) = create_temp_csv(csv_content)?;
+    let output_path = temp_dir.path().join("output.csv");
+    let output_path = output_path.to_str().unwrap();
+    let dialect = CsvDialect {
+        quote: b'\'',
+        escape: Some(b'\\'),
+    };
+
+    let stats = CsvModifier::new()
+        .with_input_dialect(dialect)
+        .process_file(&input_path, output_path)?;
+    assert_eq!(stats.total_rows, 1);
+
+    let output = std::fs::read_to_string(output_path)?;
+    let mut lines = output.lines();
+    assert_eq!(lines.next(), Some("accessIdentifier,title,field_description,field_identifier"));
+    assert_eq!(
+        lines.next(),
+        Some("2024_19_01_001,\"Smith, Jane\",She said 'hello' twice,2024_19_01_001")
+    );
+
+    // With the same dialect on output, the quoting round-trips.
+    let round_trip_path = temp_dir.path().join("round_trip.csv");
+    CsvModifier::new()
+        .with_input_dialect(dialect)
+        .with_output_dialect(dialect)
+        .process_file(&input_path, round_trip_path.to_str().unwrap())?;
+    let round_trip = std::fs::read_to_string(&round_trip_path)?;
+    assert!(round_trip.contains("2024_19_01_001,'Smith, Jane',"), "{}", round_trip);
+
+    Ok(())
+}
+
+/// A title split by an unquoted comma is rejoined only when repair is enabled
+#[test]
+fn test_repair_unquoted_comma_in_title() -> Result<(), Box<dyn std::error::Error>> {
+    let csv_content = "accessIdentifier,title,field_description\n\
+                       2024_19_01_001,Report, 2024,Annual summary\n\
+                       2024_19_01_002,Minutes,Board meeting\n";
+    let (input_path, temp_dir) = create_temp_csv(csv_content)?;
+    let output_path = temp_dir.path().join("output.csv");
+    let output_path = output_path.to_str().unwrap();
+
+    assert!(CsvModifier::new().process_file(&input_path, output_path).is_err());
+
+    let stats = CsvModifier::new()
+        .with_unquoted_comma_repair()
+        .process_file(&input_path, output_path)?;
+    assert_eq!(stats.unquoted_comma_repairs, 1);
+    assert_eq!(stats.total_rows, 2);
+
+    let output = std::fs::read_to_string(output_path)?;
+    assert!(
+        output.contains("2024_19_01_001,\"Report, 2024\",Annual summary,"),
+        "{}",
+        output
+    );
+    assert!(output.contains("2024_19_01_002,Minutes,Board meeting,"), "{}", output);
+
+    // Two extra fields cannot be attributed to the title alone.
+    let (bad_path, _bad_dir) = create_temp_csv(
+        "accessIdentifier,title,field_description\n2024_19_01_001,Report, 2024, draft,Annual summary\n",
+    )?;
+    let err = CsvModifier::new()
+        .with_unquoted_comma_repair()
+        .process_file(&bad_path, output_path)
+        .unwrap_err();
+    assert!(err.to_string().contains("has 5 fields but the header has 3"), "{}", err);
+
+    Ok(())
+}
+
+/// Pure ASCII input takes the sanitization fast path and must round-trip unchanged
+#[test]
+fn test_large_ascii_input_passes_through_unchanged() -> Result<(), Box<dyn std::error::Error>> {
+    let mut csv_content = String::from("title,notes,box\n");
+    for i in 1..=20_000 {
+        csv_content.push_str(&format!(
+            "Document {},Plain ASCII notes for row {} - no special characters,box_{:05}\n",
+            i, i, i
+        ));
+    }
+
+    let (input_path, _temp_dir) = create_temp_csv(&csv_content)?;
+    let output_path = format!("{}_output.csv", input_path);
+
+    let modifier = CsvModifier::new();
+
+    let stats = modifier.process_file(&input_path, &output_path)?;
+
+    assert_eq!(stats.total_rows, 20_000);
+    assert_eq!(stats.cells_modified, 0);
+    assert_eq!(
+        (stats.cells_sanitized, stats.cells_modified_by_modifiers, stats.cells_subdelimiter_replaced),
+        (0, 0, 0)
+    );
+    assert_eq!(stats.validation_failures, 0);
+
+    let output_content = std::fs::read_to_string(&output_path)?;
+    assert_eq!(output_content, csv_content);
+
+    Ok(())
+}
+
 /// Test cross-column modifier functionality
 #[test]
 fn test_cross_column_modifier_integration() -> Result<(), Box<dyn std::error::Error>> {
@@ -424,6 +1196,10 @@ Bob,,,bob@example.com
 
     assert_eq!(stats.total_rows, 4);
     assert_eq!(stats.cells_modified, 4);
+    assert_eq!(
+        (stats.cells_sanitized, stats.cells_modified_by_modifiers, stats.cells_subdelimiter_replaced),
+        (0, 4, 0)
+    );
 
     let output_content = std::fs::read_to_string(&output_path)?;
     assert!(output_content.contains("John Doe"));
@@ -480,6 +1256,10 @@ fn test_multiple_modifiers_integration() -> Result<(), Box<dyn std::error::Error
 
     assert_eq!(stats.total_rows, 2);
     assert_eq!(stats.cells_modified, 8); // 2 rows × 4 modifiers
+    assert_eq!(
+        (stats.cells_sanitized, stats.cells_modified_by_modifiers, stats.cells_subdelimiter_replaced),
+        (0, 8, 0)
+    );
     assert_eq!(stats.validation_failures, 0);
     assert_eq!(stats.columns_processed.len(), 5);
 
@@ -496,6 +1276,177 @@ fn test_multiple_modifiers_integration() -> Result<(), Box<dyn std::error::Error
     Ok(())
 }
 
+/// Test chaining several modifiers on the same column
+#[test]
+fn test_chained_modifiers_run_in_order() -> Result<(), Box<dyn std::error::Error>> {
+    struct ReplaceModifier {
+        from: &'static str,
+        to: &'static str,
+    }
+
+    impl ColumnModifier for ReplaceModifier {
+        fn modify(&self, value: &str, _context: &RowContext) -> String {
+            value.replace(self.from, self.to)
+        }
+
+        fn description(&self) -> &str {
+            "Substitutes text"
+        }
+    }
+
+    struct TruncateModifier {
+        max_chars: usize,
+    }
+
+    impl ColumnModifier for TruncateModifier {
+        fn modify(&self, value: &str, _context: &RowContext) -> String {
+            value.chars().take(self.max_chars).collect()
+        }
+
+        fn description(&self) -> &str {
+            "Truncates text"
+        }
+    }
+
+    let csv_content = r#"title,category
+Annual Rpt for the Board,reports
+Short,misc"#;
+
+    let (input_path, _temp_dir) = create_temp_csv(csv_content)?;
+    let output_path = format!("{}_output.csv", input_path);
+
+    let modifier = CsvModifier::new()
+        .chain_column_modifier(
+            "title",
+            ReplaceModifier {
+                from: "Rpt",
+                to: "Report",
+            },
+        )
+        .chain_column_modifier("title", TruncateModifier { max_chars: 13 });
+
+    let stats = modifier.process_file(&input_path, &output_path)?;
+
+    assert_eq!(stats.total_rows, 2);
+    assert_eq!(stats.cells_modified, 2); // substitution + truncation on the first row
+    assert_eq!(
+        (stats.cells_sanitized, stats.cells_modified_by_modifiers, stats.cells_subdelimiter_replaced),
+        (0, 2, 0)
+    );
+    assert_eq!(stats.columns_processed.len(), 3);
+
+    let output_content = std::fs::read_to_string(&output_path)?;
+    // Truncation sees the substituted value ("Annual Report for the Board")
+    assert!(output_content.contains("Annual Report,reports"));
+    assert!(output_content.contains("Short,misc"));
+
+    Ok(())
+}
+
+/// Group items on a differently-named parent column end-to-end
+#[test]
+fn test_parent_id_column_override_end_to_end() -> Result<(), Box<dyn std::error::Error>> {
+    let csv_content = r#"accessIdentifier,file,file_extension,fileTitle
+2024_19_01_001,document,pdf,Annual Report
+2024_19_01_002,image,jpg,Annual Report
+2024_20_02_001,report,pdf,Newsletter"#;
+
+    let (input_path, temp_dir) = create_temp_csv(csv_content)?;
+    let output_path = temp_dir.path().join("processed.csv");
+    let items_path = temp_dir.path().join("items.csv");
+
+    let processing_options = ProcessingOptions {
+        parent_id_column: Some("compound_id".to_string()),
+        ..Default::default()
+    };
+    let items_options = ItemGenerationOptions {
+        parent_id_column: Some("compound_id".to_string()),
+        ..Default::default()
+    };
+
+    let result = process_csv_and_maybe_generate_items(
+        &input_path,
+        output_path.to_str(),
+        None,
+        &[Modifier::ParentId],
+        &[],
+        None,
+        &processing_options,
+        true,
+        items_path.to_str(),
+        None,
+        &items_options,
+    )?;
+
+    assert_eq!(result.processing_stats.total_rows, 3);
+
+    let processed = std::fs::read_to_string(&output_path)?;
+    assert_eq!(
+        processed.lines().next().unwrap(),
+        "accessIdentifier,file,file_extension,fileTitle,compound_id,field_identifier"
+    );
+
+    let items_stats = result.items_stats.expect("items should be generated");
+    assert_eq!(items_stats.unique_parents, 2);
+
+    let items = std::fs::read_to_string(&items_path)?;
+    assert!(items.contains("2024_19_01,Annual Report,2,"));
+    assert!(items.contains("2024_20_02,Newsletter,1,"));
+
+    Ok(())
+}
+
+/// Kept container rows seed their parent's items title and are not counted as items
+#[test]
+fn test_promoted_containers_end_to_end() -> Result<(), Box<dyn std::error::Error>> {
+    let csv_content = r#"accessIdentifier,fileTitle
+2024_19_01_000,Singh Family Correspondence
+2024_19_01_001,Letter to Vancouver
+2024_19_01_002,Letter to Victoria
+2024_19_02_001,Untitled Photographs"#;
+
+    let (input_path, temp_dir) = create_temp_csv(csv_content)?;
+    let output_path = temp_dir.path().join("processed.csv");
+    let items_path = temp_dir.path().join("items.csv");
+
+    let processing_options = ProcessingOptions {
+        keep_containers: true,
+        ..Default::default()
+    };
+    let items_options = ItemGenerationOptions {
+        promote_containers: true,
+        ..Default::default()
+    };
+
+    let result = process_csv_and_maybe_generate_items(
+        &input_path,
+        output_path.to_str(),
+        None,
+        &[Modifier::ParentId],
+        &[],
+        None,
+        &processing_options,
+        true,
+        items_path.to_str(),
+        None,
+        &items_options,
+    )?;
+
+    assert_eq!(result.processing_stats.total_rows, 4);
+    assert_eq!(result.processing_stats.skipped_rows, 0);
+
+    let items_stats = result.items_stats.expect("items should be generated");
+    assert_eq!(items_stats.unique_parents, 2);
+    assert_eq!(items_stats.total_items, 3);
+    assert_eq!(items_stats.containers_promoted, 1);
+
+    let items = std::fs::read_to_string(&items_path)?;
+    assert!(items.contains("2024_19_01,Singh Family Correspondence,2,"), "{}", items);
+    assert!(items.contains("2024_19_02,Untitled Photographs,1,"), "{}", items);
+
+    Ok(())
+}
+
 /// Test Google Sheets URL conversion functionality
 #[test]
 fn test_google_sheets_url_conversion_integration() -> Result<(), Box<dyn std::error::Error>> {
@@ -586,6 +1537,10 @@ fn test_performance_integration() -> Result<(), Box<dyn std::error::Error>> {
     // Verify processing completed successfully
     assert_eq!(stats.total_rows, 1000);
     assert_eq!(stats.cells_modified, 3000); // 1000 rows × 3 modifiers
+    assert_eq!(
+        (stats.cells_sanitized, stats.cells_modified_by_modifiers, stats.cells_subdelimiter_replaced),
+        (0, 3000, 0)
+    );
     assert_eq!(stats.validation_failures, 0);
 
     // Performance should be reasonable (less than 1 second for 1000 rows)
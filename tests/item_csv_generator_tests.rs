@@ -1,5 +1,8 @@
 use anyhow::Result;
-use organise::ItemCsvGenerator;
+use organise::{
+    DateAverageRounding, ItemAggregate, ItemCsvGenerator, ItemErrorPolicy, ItemGenerationOptions,
+    SeasonConvention,
+};
 use std::fs::File;
 use std::io::Write;
 use tempfile::tempdir;
@@ -85,6 +88,30 @@ fn test_generate_items_missing_column() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_generate_items_all_empty_parent_ids_errors_with_hint() {
+    let dir = tempdir().unwrap();
+    let input_path = dir.path().join("modified.csv");
+    let output_path = dir.path().join("items.csv");
+
+    let csv_content = "accessIdentifier,parent_id,fileTitle,file\n\
+                      2024_19_01_001,,Annual Report 2024,document1.pdf\n\
+                      2024_19_01_002, ,Annual Report 2024,document2.pdf\n";
+    create_test_csv(input_path.to_str().unwrap(), csv_content).unwrap();
+
+    let err = ItemCsvGenerator::generate(
+        input_path.to_str().unwrap(),
+        output_path.to_str().unwrap(),
+        None,
+    )
+    .unwrap_err();
+
+    let message = err.to_string();
+    assert!(message.contains("'parent_id' is empty in all 2 rows"), "{}", message);
+    assert!(message.contains("parent_id modifier"), "{}", message);
+    assert!(!output_path.exists(), "no empty items file should be written");
+}
+
 #[test]
 fn test_generate_items_title_priority() -> Result<()> {
     let dir = tempdir()?;
@@ -166,3 +193,450 @@ fn test_generate_items_populates_node_column() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_generate_items_sums_count_column() -> Result<()> {
+    let dir = tempdir()?;
+    let input_path = dir.path().join("modified.csv");
+    let output_path = dir.path().join("items.csv");
+
+    let csv_content = "accessIdentifier,parent_id,fileTitle,physical_count\n\
+                      2024_19_01_001,2024_19_01,Annual Report 2024,4\n\
+                      2024_19_01_002,2024_19_01,Annual Report 2024,\n\
+                      2024_19_01_003,2024_19_01,Annual Report 2024,6\n\
+                      2024_20_01_001,2024_20_01,Monthly Newsletter,\n\
+                      2024_20_01_002,2024_20_01,Monthly Newsletter,\n";
+
+    create_test_csv(input_path.to_str().unwrap(), csv_content)?;
+
+    let options = ItemGenerationOptions {
+        count_column: Some("physical_count".to_string()),
+        ..Default::default()
+    };
+    let stats = ItemCsvGenerator::generate_with_options(
+        input_path.to_str().unwrap(),
+        output_path.to_str().unwrap(),
+        None,
+        &options,
+    )?;
+
+    assert_eq!(stats.unique_parents, 2);
+    assert_eq!(stats.total_items, 5);
+
+    let output_content = std::fs::read_to_string(&output_path)?;
+    assert!(output_content.contains("2024_19_01,Annual Report 2024,10,"));
+    // No counts recorded for this parent, so rows are counted instead
+    assert!(output_content.contains("2024_20_01,Monthly Newsletter,2,"));
+
+    Ok(())
+}
+
+#[test]
+fn test_generate_items_missing_count_column_falls_back_to_rows() -> Result<()> {
+    let dir = tempdir()?;
+    let input_path = dir.path().join("modified.csv");
+    let output_path = dir.path().join("items.csv");
+
+    let csv_content = "accessIdentifier,parent_id,fileTitle\n\
+                      2024_19_01_001,2024_19_01,Annual Report 2024\n\
+                      2024_19_01_002,2024_19_01,Annual Report 2024\n";
+
+    create_test_csv(input_path.to_str().unwrap(), csv_content)?;
+
+    let options = ItemGenerationOptions {
+        count_column: Some("physical_count".to_string()),
+        ..Default::default()
+    };
+    ItemCsvGenerator::generate_with_options(
+        input_path.to_str().unwrap(),
+        output_path.to_str().unwrap(),
+        None,
+        &options,
+    )?;
+
+    let output_content = std::fs::read_to_string(&output_path)?;
+    assert!(output_content.contains("2024_19_01,Annual Report 2024,2,"));
+
+    Ok(())
+}
+
+fn generate_season_items(convention: SeasonConvention) -> Result<String> {
+    let dir = tempdir()?;
+    let input_path = dir.path().join("modified.csv");
+    let output_path = dir.path().join("items.csv");
+
+    let csv_content = "accessIdentifier,parent_id,fileTitle,field_date\n\
+                      a_001,spring,Spring Item,Spring 2019\n\
+                      b_001,summer,Summer Item,Summer 1998\n\
+                      c_001,autumn,Autumn Item,Autumn 2020\n\
+                      d_001,fall,Fall Item,fall 2021\n\
+                      e_001,winter,Winter Item,\"Winter, 2017\"\n\
+                      f_001,q1,Q1 Item,Q1 2019\n\
+                      g_001,q2,Q2 Item,Q2 2019\n\
+                      h_001,q3,Q3 Item,q3 2019\n\
+                      i_001,q4,Q4 Item,Q4 2019\n\
+                      j_001,plain,Plain Item,Report 2019\n\
+                      k_001,accented,Montréal 2019,\n";
+
+    create_test_csv(input_path.to_str().unwrap(), csv_content)?;
+
+    let options = ItemGenerationOptions {
+        season_convention: convention,
+        ..Default::default()
+    };
+    ItemCsvGenerator::generate_with_options(
+        input_path.to_str().unwrap(),
+        output_path.to_str().unwrap(),
+        None,
+        &options,
+    )?;
+
+    Ok(std::fs::read_to_string(&output_path)?)
+}
+
+#[test]
+fn test_generate_items_maps_seasons_and_quarters_to_months() -> Result<()> {
+    let output_content = generate_season_items(SeasonConvention::Month)?;
+
+    assert!(output_content.contains("spring,Spring Item,1,,03/2019,"));
+    assert!(output_content.contains("summer,Summer Item,1,,06/1998,"));
+    assert!(output_content.contains("autumn,Autumn Item,1,,09/2020,"));
+    assert!(output_content.contains("fall,Fall Item,1,,09/2021,"));
+    assert!(output_content.contains("winter,Winter Item,1,,12/2017,"));
+    assert!(output_content.contains("q1,Q1 Item,1,,01/2019,"));
+    assert!(output_content.contains("q2,Q2 Item,1,,04/2019,"));
+    assert!(output_content.contains("q3,Q3 Item,1,,07/2019,"));
+    assert!(output_content.contains("q4,Q4 Item,1,,10/2019,"));
+    assert!(output_content.contains("plain,Plain Item,1,,2019,"));
+    // A non-ASCII letter right before the year is not a season word
+    assert!(output_content.contains("accented,Montréal 2019,1,,2019,"));
+
+    Ok(())
+}
+
+#[test]
+fn test_generate_items_maps_seasons_and_quarters_to_edtf_codes() -> Result<()> {
+    let output_content = generate_season_items(SeasonConvention::Edtf)?;
+
+    assert!(output_content.contains("spring,Spring Item,1,,2019-21,"));
+    assert!(output_content.contains("summer,Summer Item,1,,1998-22,"));
+    assert!(output_content.contains("autumn,Autumn Item,1,,2020-23,"));
+    assert!(output_content.contains("fall,Fall Item,1,,2021-23,"));
+    assert!(output_content.contains("winter,Winter Item,1,,2017-24,"));
+    assert!(output_content.contains("q1,Q1 Item,1,,2019-33,"));
+    assert!(output_content.contains("q2,Q2 Item,1,,2019-34,"));
+    assert!(output_content.contains("q3,Q3 Item,1,,2019-35,"));
+    assert!(output_content.contains("q4,Q4 Item,1,,2019-36,"));
+    assert!(output_content.contains("plain,Plain Item,1,,2019,"));
+    // A non-ASCII letter right before the year is not a season word
+    assert!(output_content.contains("accented,Montréal 2019,1,,2019,"));
+
+    Ok(())
+}
+
+fn generate_averaged_year(rounding: DateAverageRounding) -> Result<String> {
+    let dir = tempdir()?;
+    let input_path = dir.path().join("modified.csv");
+    let output_path = dir.path().join("items.csv");
+
+    // No month dominates, so the group falls back to the mean year (2019.5)
+    let csv_content = "accessIdentifier,parent_id,fileTitle,field_date\n\
+                      a_001,box,Letters,2018\n\
+                      a_002,box,Letters,2021\n";
+
+    create_test_csv(input_path.to_str().unwrap(), csv_content)?;
+
+    let options = ItemGenerationOptions {
+        date_average_rounding: rounding,
+        ..Default::default()
+    };
+    ItemCsvGenerator::generate_with_options(
+        input_path.to_str().unwrap(),
+        output_path.to_str().unwrap(),
+        None,
+        &options,
+    )?;
+
+    Ok(std::fs::read_to_string(&output_path)?)
+}
+
+#[test]
+fn test_generate_items_rounds_averaged_year() -> Result<()> {
+    let nearest = generate_averaged_year(DateAverageRounding::Nearest)?;
+    assert!(nearest.contains("box,Letters,2,,2020,"), "{}", nearest);
+
+    let floor = generate_averaged_year(DateAverageRounding::Floor)?;
+    assert!(floor.contains("box,Letters,2,,2019,"), "{}", floor);
+
+    let ceil = generate_averaged_year(DateAverageRounding::Ceil)?;
+    assert!(ceil.contains("box,Letters,2,,2020,"), "{}", ceil);
+
+    Ok(())
+}
+
+#[test]
+fn test_generate_items_aggregates_distinct_subjects() -> Result<()> {
+    let dir = tempdir()?;
+    let input_path = dir.path().join("modified.csv");
+    let output_path = dir.path().join("items.csv");
+
+    let csv_content = "accessIdentifier,parent_id,fileTitle,field_subject,field_creator\n\
+                      2024_19_01_001,2024_19_01,Letters,Railways|Immigration,Singh\n\
+                      2024_19_01_002,2024_19_01,Letters,Immigration| Farming,Singh\n\
+                      2024_19_02_001,2024_19_02,Photos,,Kaur\n";
+
+    create_test_csv(input_path.to_str().unwrap(), csv_content)?;
+
+    let options = ItemGenerationOptions {
+        aggregates: vec![
+            ItemAggregate::parse("field_subject:distinct")?,
+            ItemAggregate::parse("field_creator")?,
+        ],
+        ..Default::default()
+    };
+    ItemCsvGenerator::generate_with_options(
+        input_path.to_str().unwrap(),
+        output_path.to_str().unwrap(),
+        None,
+        &options,
+    )?;
+
+    let output_content = std::fs::read_to_string(&output_path)?;
+    let lines: Vec<&str> = output_content.lines().collect();
+    assert!(lines[0].ends_with(",field_archival_level,field_subject,field_creator"));
+    assert!(lines[1].ends_with(",File,Railways|Immigration|Farming,Singh|Singh"));
+    assert!(lines[2].ends_with(",File,,Kaur"));
+
+    assert!(ItemAggregate::parse("field_subject:unique").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_promoted_containers_are_not_items() -> Result<()> {
+    let dir = tempdir()?;
+    let input_path = dir.path().join("modified.csv");
+    let output_path = dir.path().join("items.csv");
+
+    let csv_content = "accessIdentifier,parent_id,fileTitle\n\
+                      2024_19_01_000,2024_19_01,Singh Family Correspondence\n\
+                      2024_19_01_001,2024_19_01,Letter to Vancouver\n\
+                      2024_19_01_002,2024_19_01,Letter to Victoria\n\
+                      2024_19_02_000,2024_19_02,Empty Box\n\
+                      2024_19_03_001,2024_19_03,Untitled Photographs\n";
+
+    create_test_csv(input_path.to_str().unwrap(), csv_content)?;
+
+    let options = ItemGenerationOptions {
+        promote_containers: true,
+        ..Default::default()
+    };
+    let stats = ItemCsvGenerator::generate_with_options(
+        input_path.to_str().unwrap(),
+        output_path.to_str().unwrap(),
+        None,
+        &options,
+    )?;
+
+    assert_eq!(stats.total_items, 3);
+    assert_eq!(stats.containers_promoted, 1);
+    assert_eq!(stats.unique_parents, 2);
+
+    let output_content = std::fs::read_to_string(&output_path)?;
+    assert!(output_content.contains("2024_19_01,Singh Family Correspondence,2,"), "{}", output_content);
+    assert!(output_content.contains("2024_19_03,Untitled Photographs,1,"), "{}", output_content);
+    // A parent with only a container row has no items to list.
+    assert!(!output_content.contains("2024_19_02"), "{}", output_content);
+
+    Ok(())
+}
+
+#[test]
+fn test_append_with_ledger_never_counts_a_row_twice() -> Result<()> {
+    let dir = tempdir()?;
+    let first_path = dir.path().join("batch1.csv");
+    let second_path = dir.path().join("batch2.csv");
+    let output_path = dir.path().join("items.csv");
+    let ledger_path = dir.path().join("items-ledger.csv");
+
+    create_test_csv(
+        first_path.to_str().unwrap(),
+        "accessIdentifier,parent_id,fileTitle\n\
+         2024_19_01_001,2024_19_01,Annual Report 2024\n\
+         2024_19_01_002,2024_19_01,Annual Report 2024\n\
+         2024_20_01_001,2024_20_01,Monthly Newsletter\n",
+    )?;
+    // Overlaps the first batch by one row and adds a new row for each parent
+    create_test_csv(
+        second_path.to_str().unwrap(),
+        "accessIdentifier,parent_id,fileTitle\n\
+         2024_19_01_002,2024_19_01,Annual Report 2024\n\
+         2024_19_01_003,2024_19_01,Annual Report 2024\n\
+         2024_21_01_001,2024_21_01,Photographs\n",
+    )?;
+
+    let options = ItemGenerationOptions {
+        append: true,
+        ledger: Some(ledger_path.to_string_lossy().into_owned()),
+        ..Default::default()
+    };
+    let generate = |input: &std::path::Path| {
+        ItemCsvGenerator::generate_with_options(
+            input.to_str().unwrap(),
+            output_path.to_str().unwrap(),
+            None,
+            &options,
+        )
+    };
+
+    generate(&first_path)?;
+    let first_output = std::fs::read_to_string(&output_path)?;
+    assert!(first_output.contains("2024_19_01,Annual Report 2024,2,"));
+    assert!(first_output.contains("2024_20_01,Monthly Newsletter,1,"));
+
+    let stats = generate(&first_path)?;
+    assert_eq!(stats.ledger_skipped_rows, 3);
+    assert_eq!(std::fs::read_to_string(&output_path)?, first_output);
+
+    let stats = generate(&second_path)?;
+    assert_eq!(stats.ledger_skipped_rows, 1);
+    assert_eq!(stats.unique_parents, 2);
+    let output_content = std::fs::read_to_string(&output_path)?;
+    assert!(output_content.contains("2024_19_01,Annual Report 2024,3,"));
+    assert!(output_content.contains("2024_20_01,Monthly Newsletter,1,"));
+    assert!(output_content.contains("2024_21_01,Photographs,1,"));
+
+    let ledger = std::fs::read_to_string(&ledger_path)?;
+    assert_eq!(ledger.lines().next(), Some("accessIdentifier,parent_id"));
+    assert_eq!(ledger.lines().count(), 6);
+
+    Ok(())
+}
+
+#[test]
+fn test_ledger_does_not_count_rows_without_access_identifier() -> Result<()> {
+    let dir = tempdir()?;
+    let input_path = dir.path().join("batch.csv");
+    let output_path = dir.path().join("items.csv");
+
+    create_test_csv(
+        input_path.to_str().unwrap(),
+        "accessIdentifier,parent_id,fileTitle\n\
+         2024_19_01_001,2024_19_01,Annual Report 2024\n\
+         ,2024_19_01,Annual Report 2024\n",
+    )?;
+
+    let options = ItemGenerationOptions {
+        append: true,
+        ledger: Some(dir.path().join("ledger.csv").to_string_lossy().into_owned()),
+        ..Default::default()
+    };
+    for _ in 0..2 {
+        let stats = ItemCsvGenerator::generate_with_options(
+            input_path.to_str().unwrap(),
+            output_path.to_str().unwrap(),
+            None,
+            &options,
+        )?;
+        assert_eq!(stats.ledger_untracked_rows, 1);
+    }
+
+    let output_content = std::fs::read_to_string(&output_path)?;
+    assert!(output_content.contains("2024_19_01,Annual Report 2024,1,"));
+
+    Ok(())
+}
+
+#[test]
+fn test_generate_items_single_input_honours_output_dir() -> Result<()> {
+    let dir = tempdir()?;
+    let input_path = dir.path().join("modified.csv");
+    let output_dir = dir.path().join("out");
+
+    create_test_csv(
+        input_path.to_str().unwrap(),
+        "accessIdentifier,parent_id,fileTitle\n2024_19_01_001,2024_19_01,Annual Report 2024\n",
+    )?;
+
+    let status = std::process::Command::new(env!("CARGO_BIN_EXE_organise"))
+        .current_dir(dir.path())
+        .arg("generate-items")
+        .arg(&input_path)
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .status()?;
+
+    assert!(status.success());
+    assert!(output_dir.join("items.csv").exists());
+    assert!(!dir.path().join("items.csv").exists());
+
+    Ok(())
+}
+
+fn create_batch_inputs(dir: &std::path::Path) -> Result<Vec<(String, String)>> {
+    let contents = [
+        "parent_id,fileTitle\n2024_19_01,Box 1 Letters\n",
+        "accessIdentifier,fileTitle\n2024_19_02_001,Missing Parent\n",
+        "parent_id,fileTitle\n2024_19_03,Box 3 Photos\n2024_19_03,Box 3 Photos\n",
+    ];
+
+    let mut jobs = Vec::new();
+    for (idx, content) in contents.iter().enumerate() {
+        let input = dir.join(format!("box{}.csv", idx + 1));
+        let output = dir.join(format!("box{}-items.csv", idx + 1));
+        create_test_csv(input.to_str().unwrap(), content)?;
+        jobs.push((
+            input.to_string_lossy().into_owned(),
+            output.to_string_lossy().into_owned(),
+        ));
+    }
+    Ok(jobs)
+}
+
+#[test]
+fn test_generate_batch_continues_past_failing_input() -> Result<()> {
+    let dir = tempdir()?;
+    let jobs = create_batch_inputs(dir.path())?;
+    let job_refs: Vec<(&str, &str)> = jobs.iter().map(|(i, o)| (i.as_str(), o.as_str())).collect();
+
+    let report = ItemCsvGenerator::generate_batch(
+        &job_refs,
+        None,
+        &ItemGenerationOptions::default(),
+        ItemErrorPolicy::Continue,
+    )?;
+
+    let generated: Vec<&str> = report.generated.iter().map(|(input, _, _)| input.as_str()).collect();
+    assert_eq!(generated, vec![jobs[0].0.as_str(), jobs[2].0.as_str()]);
+    assert_eq!(report.generated[1].2.total_items, 2);
+
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].0, jobs[1].0);
+    assert!(report.failed[0].1.to_string().contains("parent_id"));
+
+    assert!(std::path::Path::new(&jobs[2].1).exists());
+    assert!(!std::path::Path::new(&jobs[1].1).exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_generate_batch_aborts_on_first_failing_input() -> Result<()> {
+    let dir = tempdir()?;
+    let jobs = create_batch_inputs(dir.path())?;
+    let job_refs: Vec<(&str, &str)> = jobs.iter().map(|(i, o)| (i.as_str(), o.as_str())).collect();
+
+    let err = ItemCsvGenerator::generate_batch(
+        &job_refs,
+        None,
+        &ItemGenerationOptions::default(),
+        ItemErrorPolicy::Abort,
+    )
+    .unwrap_err();
+
+    assert!(err.to_string().contains("box2.csv"));
+    assert!(std::path::Path::new(&jobs[0].1).exists());
+    assert!(!std::path::Path::new(&jobs[2].1).exists());
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+//! Log level classification tests.
+//!
+//! Kept in their own test binary because they install a process-wide capturing logger.
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use organise::{CsvModifier, ParentIdModifier, TRANSFORM_LOG_TARGET};
+use std::fs::File;
+use std::io::Write;
+use std::sync::Mutex;
+use tempfile::tempdir;
+
+struct CaptureLogger {
+    records: Mutex<Vec<(Level, String, String)>>,
+}
+
+impl Log for CaptureLogger {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        self.records.lock().unwrap().push((
+            record.level(),
+            record.target().to_string(),
+            record.args().to_string(),
+        ));
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CaptureLogger = CaptureLogger {
+    records: Mutex::new(Vec::new()),
+};
+
+/// Validation failures are warnings; applied-modifier notices sit below warn on the transform target
+#[test]
+fn test_validation_warns_and_modifier_activity_does_not() -> Result<(), Box<dyn std::error::Error>> {
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(LevelFilter::Trace);
+
+    let dir = tempdir()?;
+    let input_path = dir.path().join("input.csv");
+    let output_path = dir.path().join("output.csv");
+    File::create(&input_path)?.write_all(
+        b"accessIdentifier,title\n2024_19_01_001,Report\n2024_19_01_001,Report\n",
+    )?;
+
+    let modifier = CsvModifier::new().add_column_modifier("parent_id", ParentIdModifier);
+    modifier.process_file(input_path.to_str().unwrap(), output_path.to_str().unwrap())?;
+
+    let records = LOGGER.records.lock().unwrap();
+
+    let duplicate = records
+        .iter()
+        .find(|(_, _, message)| message.contains("Duplicate accessIdentifier"))
+        .expect("duplicate accessIdentifier should be logged");
+    assert_eq!(duplicate.0, Level::Warn);
+
+    let applied: Vec<_> = records
+        .iter()
+        .filter(|(_, _, message)| message.starts_with("Applying modifier"))
+        .collect();
+    assert!(!applied.is_empty(), "applied modifiers should be logged");
+    for (level, target, message) in &applied {
+        assert!(*level > Level::Warn, "{} logged at {}", message, level);
+        assert_eq!(target, TRANSFORM_LOG_TARGET);
+    }
+
+    let changed = records
+        .iter()
+        .find(|(_, _, message)| message.contains("column 'parent_id' changed"))
+        .expect("parent_id cell change should be logged");
+    assert_eq!(changed.0, Level::Debug);
+    assert_eq!(changed.1, TRANSFORM_LOG_TARGET);
+
+    Ok(())
+}